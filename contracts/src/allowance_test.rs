@@ -0,0 +1,101 @@
+#![cfg(test)]
+
+use crate::test_helpers::{setup, TestContext};
+use soroban_sdk::testutils::Ledger;
+
+fn approve(ctx: &TestContext, amount: i128) {
+    ctx.token
+        .approve(&ctx.sender, &ctx.client.address, &amount, &10_000);
+}
+
+#[test]
+fn test_withdraw_pulls_from_allowance() {
+    let ctx = setup();
+    approve(&ctx, 1000);
+
+    let stream_id = ctx.client.create_allowance_stream(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token_id,
+        &1000,
+        &0,
+        &1000,
+    );
+
+    // Nothing moves at creation
+    assert_eq!(ctx.token.balance(&ctx.sender), 10_000);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 0);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 400);
+    assert_eq!(ctx.client.withdraw(&stream_id, &ctx.receiver), 400);
+    assert_eq!(ctx.token.balance(&ctx.receiver), 400);
+    assert_eq!(ctx.token.balance(&ctx.sender), 9_600);
+    assert_eq!(ctx.token.allowance(&ctx.sender, &ctx.client.address), 600);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient allowance to pay stream")]
+fn test_withdraw_rejects_insufficient_allowance() {
+    let ctx = setup();
+    approve(&ctx, 100);
+
+    let stream_id = ctx.client.create_allowance_stream(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token_id,
+        &1000,
+        &0,
+        &1000,
+    );
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 400);
+    ctx.client.withdraw(&stream_id, &ctx.receiver);
+}
+
+#[test]
+fn test_failed_withdraw_is_rolled_back() {
+    let ctx = setup();
+    approve(&ctx, 100);
+
+    let stream_id = ctx.client.create_allowance_stream(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token_id,
+        &1000,
+        &0,
+        &1000,
+    );
+
+    // The pull fails after the stream was updated; none of it sticks
+    ctx.env.ledger().with_mut(|li| li.timestamp = 400);
+    assert!(ctx.client.try_withdraw(&stream_id, &ctx.receiver).is_err());
+    assert_eq!(ctx.client.get_stream(&stream_id).withdrawn_amount, 0);
+
+    // The pull failed while the lock was held; it is not left stuck either
+    approve(&ctx, 1000);
+    assert_eq!(ctx.client.withdraw(&stream_id, &ctx.receiver), 400);
+    assert_eq!(ctx.token.balance(&ctx.receiver), 400);
+}
+
+#[test]
+fn test_cancel_pays_accrued_and_leaves_rest_with_sender() {
+    let ctx = setup();
+    approve(&ctx, 1000);
+
+    let stream_id = ctx.client.create_allowance_stream(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token_id,
+        &1000,
+        &0,
+        &1000,
+    );
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 250);
+    ctx.client.cancel(&stream_id, &ctx.sender);
+
+    assert_eq!(ctx.token.balance(&ctx.receiver), 250);
+    assert_eq!(ctx.token.balance(&ctx.sender), 9_750);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 0);
+}
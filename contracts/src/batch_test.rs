@@ -0,0 +1,217 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::setup_with_balance,
+    types::{MultiTokenStreamRequest, StreamRequest},
+};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, Vec,
+};
+
+fn request(env: &Env, amount: i128) -> StreamRequest {
+    StreamRequest {
+        receiver: Address::generate(env),
+        amount,
+        start_time: 0,
+        cliff_time: 0,
+        end_time: 1000,
+        interest_strategy: 0,
+        vault_address: None,
+        metadata: None,
+    }
+}
+
+#[test]
+fn test_fee_free_batch_needs_no_treasury() {
+    let ctx = setup_with_balance(100_000);
+    // No initialize_fee: there is no treasury configured at all
+    let requests = vec![&ctx.env, request(&ctx.env, 1000), request(&ctx.env, 1500)];
+
+    let ids = ctx
+        .client
+        .create_batch_streams(&ctx.sender, &ctx.token_id, &requests);
+
+    assert_eq!(ids, vec![&ctx.env, 0, 1]);
+    assert_eq!(ctx.client.get_stream(&0).total_amount, 1000);
+    assert_eq!(ctx.client.get_stream(&1).total_amount, 1500);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 2500);
+    assert_eq!(ctx.token.balance(&ctx.sender), 97_500);
+}
+
+#[test]
+fn test_batch_charges_fee_per_stream() {
+    let ctx = setup_with_balance(100_000);
+    let treasury = Address::generate(&ctx.env);
+    ctx.client.initialize_fee(&ctx.admin, &100, &treasury);
+
+    let requests = vec![&ctx.env, request(&ctx.env, 1000), request(&ctx.env, 2000)];
+    let ids = ctx
+        .client
+        .create_batch_streams(&ctx.sender, &ctx.token_id, &requests);
+
+    assert_eq!(
+        ctx.client.get_stream(&ids.get(0).unwrap()).total_amount,
+        990
+    );
+    assert_eq!(
+        ctx.client.get_stream(&ids.get(1).unwrap()).total_amount,
+        1980
+    );
+    assert_eq!(ctx.token.balance(&treasury), 30);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 2970);
+}
+
+#[test]
+fn test_invalid_request_rejects_whole_batch() {
+    let ctx = setup_with_balance(100_000);
+    let mut late_cliff = request(&ctx.env, 1000);
+    late_cliff.cliff_time = 100;
+    let requests: Vec<StreamRequest> = vec![&ctx.env, request(&ctx.env, 1000), late_cliff];
+
+    let result = ctx
+        .client
+        .try_create_batch_streams(&ctx.sender, &ctx.token_id, &requests);
+    assert_eq!(result, Err(Ok(Error::InvalidTimeRange)));
+
+    let requests = vec![&ctx.env, request(&ctx.env, 1000), request(&ctx.env, 0)];
+    let result = ctx
+        .client
+        .try_create_batch_streams(&ctx.sender, &ctx.token_id, &requests);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+    assert_eq!(ctx.token.balance(&ctx.sender), 100_000);
+}
+
+#[test]
+fn test_multi_token_batch_transfers_once_per_token() {
+    let ctx = setup_with_balance(100_000);
+    let treasury = Address::generate(&ctx.env);
+    ctx.client.initialize_fee(&ctx.admin, &100, &treasury);
+
+    let other_id = ctx
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&ctx.env))
+        .address();
+    StellarAssetClient::new(&ctx.env, &other_id).mint(&ctx.sender, &50_000);
+    let other = TokenClient::new(&ctx.env, &other_id);
+
+    let item = |token: &Address, amount: i128| MultiTokenStreamRequest {
+        token: token.clone(),
+        request: request(&ctx.env, amount),
+    };
+    let requests = vec![
+        &ctx.env,
+        item(&ctx.token_id, 1000),
+        item(&other_id, 4000),
+        item(&ctx.token_id, 2000),
+    ];
+    let ids = ctx.client.create_multi_token_batch(&ctx.sender, &requests);
+
+    assert_eq!(ids, vec![&ctx.env, 0, 1, 2]);
+    assert_eq!(ctx.client.get_stream(&0).token, ctx.token_id);
+    assert_eq!(ctx.client.get_stream(&1).token, other_id);
+    assert_eq!(ctx.client.get_stream(&1).total_amount, 3960);
+    assert_eq!(ctx.client.get_stream(&2).total_amount, 1980);
+
+    // Each token was debited for its own streams only
+    assert_eq!(ctx.token.balance(&ctx.sender), 97_000);
+    assert_eq!(other.balance(&ctx.sender), 46_000);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 2970);
+    assert_eq!(other.balance(&ctx.client.address), 3960);
+    assert_eq!(ctx.token.balance(&treasury), 30);
+    assert_eq!(other.balance(&treasury), 40);
+
+    assert_eq!(
+        ctx.client.get_streams_by_token(&other_id, &0, &10),
+        vec![&ctx.env, 1]
+    );
+}
+
+#[test]
+fn test_multi_token_batch_rejects_whole_batch() {
+    let ctx = setup_with_balance(100_000);
+    let mut bad = request(&ctx.env, 1000);
+    bad.end_time = 0;
+    let requests = vec![
+        &ctx.env,
+        MultiTokenStreamRequest {
+            token: ctx.token_id.clone(),
+            request: request(&ctx.env, 1000),
+        },
+        MultiTokenStreamRequest {
+            token: ctx.token_id.clone(),
+            request: bad,
+        },
+    ];
+
+    let result = ctx
+        .client
+        .try_create_multi_token_batch(&ctx.sender, &requests);
+    assert_eq!(result, Err(Ok(Error::InvalidTimeRange)));
+    assert_eq!(ctx.token.balance(&ctx.sender), 100_000);
+    assert!(!ctx.client.stream_exists(&0));
+}
+
+#[test]
+fn test_batch_at_size_limit_succeeds() {
+    let ctx = setup_with_balance(100_000);
+    let mut requests: Vec<StreamRequest> = Vec::new(&ctx.env);
+    for _ in 0..crate::MAX_BATCH_SIZE {
+        requests.push_back(request(&ctx.env, 1000));
+    }
+
+    let ids = ctx
+        .client
+        .create_batch_streams(&ctx.sender, &ctx.token_id, &requests);
+    assert_eq!(ids.len(), crate::MAX_BATCH_SIZE);
+    assert_eq!(
+        ctx.token.balance(&ctx.sender),
+        100_000 - 1000 * crate::MAX_BATCH_SIZE as i128
+    );
+}
+
+#[test]
+#[should_panic(expected = "Batch too large")]
+fn test_oversized_batch_rejected_before_transfer() {
+    let ctx = setup_with_balance(100_000);
+    ctx.client.set_max_batch_size(&ctx.admin, &Some(2));
+    assert_eq!(ctx.client.get_max_batch_size(), 2);
+
+    // Unfundable and invalid, yet the size check fires before either is noticed
+    let requests = vec![
+        &ctx.env,
+        request(&ctx.env, 1_000_000),
+        request(&ctx.env, 0),
+        request(&ctx.env, 1000),
+    ];
+    ctx.client
+        .create_batch_streams(&ctx.sender, &ctx.token_id, &requests);
+}
+
+#[test]
+fn test_max_batch_size_is_configurable() {
+    let ctx = setup_with_balance(100_000);
+    assert_eq!(ctx.client.get_max_batch_size(), crate::MAX_BATCH_SIZE);
+
+    ctx.client.set_max_batch_size(&ctx.admin, &Some(1));
+    let requests = vec![&ctx.env, request(&ctx.env, 1000), request(&ctx.env, 1000)];
+    assert!(ctx
+        .client
+        .try_create_batch_streams(&ctx.sender, &ctx.token_id, &requests)
+        .is_err());
+    assert_eq!(ctx.token.balance(&ctx.sender), 100_000);
+
+    // Clearing the override restores the default
+    ctx.client.set_max_batch_size(&ctx.admin, &None);
+    assert_eq!(ctx.client.get_max_batch_size(), crate::MAX_BATCH_SIZE);
+    ctx.client
+        .create_batch_streams(&ctx.sender, &ctx.token_id, &requests);
+
+    assert_eq!(
+        ctx.client
+            .try_set_max_batch_size(&Address::generate(&ctx.env), &Some(10)),
+        Err(Ok(Error::Unauthorized))
+    );
+}
@@ -0,0 +1,43 @@
+#![cfg(test)]
+
+use crate::{errors::Error, test_helpers::setup};
+use soroban_sdk::testutils::Ledger;
+
+#[test]
+fn test_cancel_within_grace_after_end_pays_receiver_in_full() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+
+    ctx.client
+        .set_cancel_after_end_grace(&ctx.admin, &Some(100));
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1_100);
+    ctx.client.cancel(&stream_id, &ctx.sender);
+
+    assert!(ctx.client.get_stream(&stream_id).cancelled);
+    assert_eq!(ctx.token.balance(&ctx.receiver), 1000);
+    assert_eq!(ctx.token.balance(&ctx.sender), 9_000);
+}
+
+#[test]
+fn test_cancel_rejected_past_grace() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+
+    ctx.client
+        .set_cancel_after_end_grace(&ctx.admin, &Some(100));
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1_101);
+    let result = ctx.client.try_cancel(&stream_id, &ctx.sender);
+    assert_eq!(result, Err(Ok(Error::StreamEnded)));
+
+    // Without a configured grace, cancelling after the end stays allowed
+    ctx.client.set_cancel_after_end_grace(&ctx.admin, &None);
+    ctx.client.cancel(&stream_id, &ctx.sender);
+    assert!(ctx.client.get_stream(&stream_id).cancelled);
+
+    let result = ctx
+        .client
+        .try_set_cancel_after_end_grace(&ctx.sender, &Some(10));
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
@@ -0,0 +1,180 @@
+#![cfg(test)]
+
+use crate::{
+    test_helpers::{setup, TestContext},
+    types::{CurveType, DataKey, StreamOptions},
+};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    token::TokenClient,
+    Address, Env,
+};
+
+/// Receiver contract that records the last cancellation callback
+#[contract]
+pub struct MockHookReceiver;
+
+#[contractimpl]
+impl MockHookReceiver {
+    pub fn on_stream_cancelled(env: Env, stream_id: u64, amount: i128) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("cancel"), &(stream_id, amount));
+    }
+
+    pub fn last_cancel(env: Env) -> Option<(u64, i128)> {
+        env.storage().instance().get(&symbol_short!("cancel"))
+    }
+}
+
+fn options(on_cancel_hook: bool) -> StreamOptions {
+    StreamOptions {
+        is_soulbound: false,
+        idempotency_key: None,
+        mutual_cancel: false,
+        revert_after: None,
+        on_cancel_hook,
+    }
+}
+
+#[test]
+fn test_cancel_notifies_receiver_contract() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        token_id,
+        ..
+    } = setup();
+    let receiver_id = env.register(MockHookReceiver, ());
+    let receiver = MockHookReceiverClient::new(&env, &receiver_id);
+
+    let stream_id = client.create_stream_with_options(
+        &sender,
+        &receiver_id,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &options(true),
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.withdraw(&stream_id, &receiver_id);
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    client.cancel(&stream_id, &sender);
+
+    // The callback sees what cancellation paid, not what was withdrawn earlier
+    assert_eq!(receiver.last_cancel(), Some((stream_id, 300)));
+    assert_eq!(TokenClient::new(&env, &token_id).balance(&receiver_id), 400);
+}
+
+#[test]
+fn test_cancel_without_hook_skips_callback() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        token_id,
+        ..
+    } = setup();
+    let receiver_id = env.register(MockHookReceiver, ());
+    let receiver = MockHookReceiverClient::new(&env, &receiver_id);
+
+    let stream_id = client.create_stream_with_options(
+        &sender,
+        &receiver_id,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &options(false),
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    client.cancel(&stream_id, &sender);
+
+    assert_eq!(receiver.last_cancel(), None);
+    assert!(client.get_stream(&stream_id).cancelled);
+    assert_eq!(TokenClient::new(&env, &token_id).balance(&receiver_id), 400);
+}
+
+#[test]
+fn test_plain_account_receiver_cancels_normally() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        token_id,
+        token,
+        ..
+    } = setup();
+    let receiver = Address::generate(&env);
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 250);
+    client.cancel(&stream_id, &sender);
+
+    assert_eq!(token.balance(&receiver), 250);
+    assert_eq!(token.balance(&sender), 9_750);
+}
+
+/// Receiver contract whose callback always fails
+#[contract]
+pub struct FailingHookReceiver;
+
+#[contractimpl]
+impl FailingHookReceiver {
+    pub fn on_stream_cancelled(_env: Env, _stream_id: u64, _amount: i128) {
+        panic!("hook failed");
+    }
+}
+
+#[test]
+fn test_failed_hook_does_not_block_cancel() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        token_id,
+        token,
+        ..
+    } = setup();
+    let receiver_id = env.register(FailingHookReceiver, ());
+
+    let stream_id = client.create_stream_with_options(
+        &sender,
+        &receiver_id,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &options(true),
+    );
+
+    // The hook panics, but the cancel settles anyway and releases the lock
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    client.cancel(&stream_id, &sender);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().temporary().has(&DataKey::ReentrancyLock));
+    });
+    assert!(client.get_stream(&stream_id).cancelled);
+
+    assert_eq!(token.balance(&receiver_id), 400);
+    assert_eq!(token.balance(&sender), 9_600);
+    assert_eq!(token.balance(&client.address), 0);
+}
@@ -0,0 +1,173 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup_with_balance, TestContext},
+    types::{CurveParams, CurveType},
+};
+use soroban_sdk::{testutils::Ledger, vec, Env, IntoVal, TryFromVal, Val, Vec};
+
+fn custom(checkpoints: Vec<(u32, u32)>) -> CurveParams {
+    CurveParams {
+        checkpoints,
+        step_interval: 0,
+    }
+}
+
+fn try_create(ctx: &TestContext, checkpoints: Vec<(u32, u32)>) -> Result<u64, Error> {
+    match ctx.client.try_create_stream_with_curve(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token_id,
+        &10_000,
+        &0,
+        &1000,
+        &CurveType::Custom,
+        &custom(checkpoints),
+    ) {
+        Ok(id) => Ok(id.unwrap()),
+        Err(Ok(err)) => Err(err),
+        Err(Err(_)) => panic!("unexpected host error"),
+    }
+}
+
+#[test]
+fn test_custom_curve_interpolates_between_checkpoints() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup_with_balance(100_000);
+    // 0% -> 0%, 50% -> 20%, 100% -> 100%
+    let stream_id = client.create_stream_with_curve(
+        &sender,
+        &receiver,
+        &token_id,
+        &10_000,
+        &0,
+        &1000,
+        &CurveType::Custom,
+        &custom(vec![&env, (0, 0), (5_000, 2_000), (10_000, 10_000)]),
+    );
+
+    let expected = [
+        (0, 0),
+        (250, 1_000), // halfway to the first checkpoint
+        (500, 2_000), // at the checkpoint
+        (750, 6_000), // halfway between 20% and 100%
+        (1000, 10_000),
+    ];
+    for (timestamp, unlocked) in expected {
+        env.ledger().with_mut(|li| li.timestamp = timestamp);
+        assert_eq!(client.get_unlocked(&stream_id), unlocked);
+    }
+}
+
+#[test]
+fn test_custom_curve_flat_segment_and_withdraw() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup_with_balance(100_000);
+    // Nothing for the first 40%, then a 25% jump spread over the next 10%
+    let stream_id = client.create_stream_with_curve(
+        &sender,
+        &receiver,
+        &token_id,
+        &10_000,
+        &0,
+        &1000,
+        &CurveType::Custom,
+        &custom(vec![
+            &env,
+            (0, 0),
+            (4_000, 0),
+            (5_000, 2_500),
+            (10_000, 10_000),
+        ]),
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    assert_eq!(client.get_unlocked(&stream_id), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 450);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 1_250);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 8_750);
+}
+
+#[test]
+fn test_custom_curve_validation() {
+    let ctx = setup_with_balance(100_000);
+
+    // Must start at (0, 0)
+    let bad_start = vec![&ctx.env, (0, 100), (10_000, 10_000)];
+    assert_eq!(try_create(&ctx, bad_start), Err(Error::InvalidCurve));
+
+    // Must end at (10000, 10000)
+    let bad_end = vec![&ctx.env, (0, 0), (10_000, 9_000)];
+    assert_eq!(try_create(&ctx, bad_end), Err(Error::InvalidCurve));
+
+    // Unlocked share may not decrease
+    let decreasing = vec![
+        &ctx.env,
+        (0, 0),
+        (4_000, 5_000),
+        (6_000, 4_000),
+        (10_000, 10_000),
+    ];
+    assert_eq!(try_create(&ctx, decreasing), Err(Error::InvalidCurve));
+
+    // Elapsed share must strictly advance
+    let repeated = vec![
+        &ctx.env,
+        (0, 0),
+        (5_000, 2_000),
+        (5_000, 3_000),
+        (10_000, 10_000),
+    ];
+    assert_eq!(try_create(&ctx, repeated), Err(Error::InvalidCurve));
+
+    // Bounded number of checkpoints
+    let mut too_many = vec![&ctx.env, (0, 0)];
+    for i in 1..=16u32 {
+        too_many.push_back((i * 600, i * 600));
+    }
+    too_many.push_back((10_000, 10_000));
+    assert_eq!(try_create(&ctx, too_many), Err(Error::InvalidCurve));
+
+    let ok = vec![&ctx.env, (0, 0), (10_000, 10_000)];
+    assert!(try_create(&ctx, ok).is_ok());
+
+    // A custom curve needs its checkpoints
+    assert_eq!(
+        try_create(&ctx, Vec::new(&ctx.env)),
+        Err(Error::InvalidCurve)
+    );
+}
+
+#[test]
+fn test_curve_type_keeps_u32_encoding() {
+    let env = Env::default();
+
+    // Clients pass the curve as a plain u32, as before custom curves existed
+    for (curve, value) in [
+        (CurveType::Linear, 0u32),
+        (CurveType::Exponential, 1),
+        (CurveType::Custom, 2),
+        (CurveType::Step, 3),
+    ] {
+        let encoded: Val = curve.clone().into_val(&env);
+        assert_eq!(u32::try_from_val(&env, &encoded).unwrap(), value);
+        let raw: Val = value.into_val(&env);
+        assert_eq!(CurveType::try_from_val(&env, &raw).unwrap(), curve);
+    }
+}
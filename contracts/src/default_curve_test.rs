@@ -0,0 +1,131 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup, TestContext},
+    types::{CurveParams, CurveType, StreamRequest},
+    StellarStreamContractClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, Vec,
+};
+
+fn params(checkpoints: Vec<(u32, u32)>, step_interval: u64) -> CurveParams {
+    CurveParams {
+        checkpoints,
+        step_interval,
+    }
+}
+
+fn create_batch_stream(
+    env: &Env,
+    client: &StellarStreamContractClient,
+    sender: &Address,
+    token_id: &Address,
+) -> u64 {
+    let request = StreamRequest {
+        receiver: Address::generate(env),
+        amount: 1000,
+        start_time: 0,
+        cliff_time: 0,
+        end_time: 1000,
+        interest_strategy: 0,
+        vault_address: None,
+        metadata: None,
+    };
+    client
+        .create_batch_streams(sender, token_id, &vec![env, request])
+        .get(0)
+        .unwrap()
+}
+
+#[test]
+fn test_default_curve_is_linear() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        token_id,
+        ..
+    } = setup();
+
+    assert_eq!(client.get_default_curve().0, CurveType::Linear);
+    let stream_id = create_batch_stream(&env, &client, &sender, &token_id);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.get_unlocked(&stream_id), 500);
+}
+
+#[test]
+fn test_nonlinear_default_changes_curveless_vesting() {
+    let TestContext {
+        env,
+        client,
+        admin,
+        sender,
+        token_id,
+        ..
+    } = setup();
+
+    client.set_default_curve(&admin, &CurveType::Exponential, &params(vec![&env], 0));
+    let stream_id = create_batch_stream(&env, &client, &sender, &token_id);
+    let allowance_id = client.create_allowance_stream(
+        &sender,
+        &Address::generate(&env),
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+    );
+
+    // Quadratic: half the time unlocks a quarter
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.get_unlocked(&stream_id), 250);
+    assert_eq!(client.get_unlocked(&allowance_id), 250);
+
+    // An explicit curve still wins
+    let explicit_id = client.create_stream(
+        &sender,
+        &Address::generate(&env),
+        &token_id,
+        &1000,
+        &500,
+        &1500,
+        &CurveType::Linear,
+        &false,
+    );
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(client.get_unlocked(&explicit_id), 500);
+}
+
+#[test]
+fn test_set_default_curve_validation() {
+    let TestContext {
+        env,
+        client,
+        admin,
+        sender,
+        ..
+    } = setup();
+
+    let none = params(Vec::new(&env), 0);
+    assert_eq!(
+        client.try_set_default_curve(&sender, &CurveType::Exponential, &none),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_default_curve(&admin, &CurveType::Step, &none),
+        Err(Ok(Error::InvalidCurve))
+    );
+    assert_eq!(
+        client.try_set_default_curve(&admin, &CurveType::Custom, &params(vec![&env, (0, 0)], 0)),
+        Err(Ok(Error::InvalidCurve))
+    );
+    assert_eq!(client.get_default_curve(), (CurveType::Linear, none));
+
+    // A valid step default is stored with its interval
+    let weekly = params(Vec::new(&env), 100);
+    client.set_default_curve(&admin, &CurveType::Step, &weekly);
+    assert_eq!(client.get_default_curve(), (CurveType::Step, weekly));
+}
@@ -29,4 +29,22 @@ pub enum Error {
     AddressRestricted = 22,
     /// Stream has already ended (past end_time)
     StreamEnded = 23,
+    /// Fee exceeds the protocol maximum (1000 bps)
+    FeeTooHigh = 24,
+    /// Treasury splits are empty or do not sum to 10000 bps
+    InvalidTreasurySplits = 25,
+    /// Contract is paused
+    ContractPaused = 26,
+    /// Milestones are out of order, outside the stream window, or above 100%
+    InvalidMilestone = 27,
+    /// Curve parameters are malformed (bad checkpoints or step interval)
+    InvalidCurve = 28,
+    /// Stream has no reversion deadline or it has not passed yet
+    RevertNotAvailable = 29,
+    /// Stream has not reached its (pause-adjusted) end_time yet
+    StreamNotEnded = 30,
+    /// No upgrade has been proposed, or it was for a different WASM hash
+    UpgradeNotProposed = 31,
+    /// The proposed upgrade's timelock has not elapsed yet
+    UpgradeTimelocked = 32,
 }
@@ -0,0 +1,206 @@
+#![cfg(test)]
+
+use crate::{
+    test_helpers::{setup, TestContext},
+    types::{CurveType, DataKey, StreamRequest},
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Events, Ledger},
+    vec, Address, Env, IntoVal, Val, Vec,
+};
+
+/// Returns the data of every event published by `contract` with the given topics.
+fn find_events(env: &Env, contract: &Address, topics: Vec<Val>) -> Vec<Val> {
+    let mut found = Vec::new(env);
+    for (event_contract, event_topics, data) in env.events().all().iter() {
+        if event_contract == *contract && event_topics == topics {
+            found.push_back(data);
+        }
+    }
+    found
+}
+
+#[test]
+fn test_stream_created_event_reconstructs_stream() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        token,
+        ..
+    } = setup();
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &100,
+        &600,
+        &CurveType::Linear,
+        &false,
+    );
+
+    let created = find_events(
+        &env,
+        &client.address,
+        (symbol_short!("stream"), symbol_short!("created")).into_val(&env),
+    );
+    assert_eq!(created.len(), 1);
+
+    let (id, ev_sender, ev_receiver, ev_token, amount, start, cliff, end, decimals): (
+        u64,
+        Address,
+        Address,
+        Address,
+        i128,
+        u64,
+        u64,
+        u64,
+        u32,
+    ) = created.get(0).unwrap().into_val(&env);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(id, stream_id);
+    assert_eq!(ev_sender, stream.sender);
+    assert_eq!(ev_receiver, stream.receiver);
+    assert_eq!(ev_token, stream.token);
+    assert_eq!(amount, stream.total_amount);
+    assert_eq!(start, stream.start_time);
+    assert_eq!(cliff, stream.start_time);
+    assert_eq!(end, stream.end_time);
+    assert_eq!(decimals, token.decimals());
+    assert_eq!(decimals, 7);
+}
+
+#[test]
+fn test_only_last_withdraw_event_is_final() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+
+    let topics: Vec<Val> = (symbol_short!("withdraw"), receiver.clone()).into_val(&env);
+    let mut flags = Vec::new(&env);
+    for (timestamp, expected) in [(300, 300), (700, 400), (1000, 300)] {
+        env.ledger().with_mut(|li| li.timestamp = timestamp);
+        client.withdraw(&stream_id, &receiver);
+
+        let events = find_events(&env, &client.address, topics.clone());
+        assert_eq!(events.len(), 1);
+        let (id, amount, is_final): (u64, i128, bool) = events.get(0).unwrap().into_val(&env);
+        assert_eq!(id, stream_id);
+        assert_eq!(amount, expected);
+        flags.push_back(is_final);
+    }
+
+    assert_eq!(flags, vec![&env, false, false, true]);
+}
+
+#[test]
+fn test_token_decimals_are_cached() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+
+    // The public getter is a plain read
+    assert_eq!(client.get_token_decimals(&token_id), 7);
+    env.as_contract(&client.address, || {
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&DataKey::TokenDecimals(token_id.clone())));
+    });
+
+    // Creating a stream fills the cache
+    client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(client.get_token_decimals(&token_id), 7);
+    env.as_contract(&client.address, || {
+        let cached: Option<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TokenDecimals(token_id.clone()));
+        assert_eq!(cached, Some(7));
+    });
+}
+
+#[test]
+fn test_batch_event_summarises_stream_ids() {
+    let TestContext {
+        env,
+        client,
+        admin,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let request = StreamRequest {
+        receiver,
+        amount: 1000,
+        start_time: 0,
+        cliff_time: 0,
+        end_time: 1000,
+        interest_strategy: 0,
+        vault_address: None,
+        metadata: None,
+    };
+    let requests = vec![&env, request.clone(), request.clone(), request.clone()];
+    let batch_topics: Vec<Val> = (symbol_short!("stream"), symbol_short!("batch")).into_val(&env);
+    let created_topics: Vec<Val> =
+        (symbol_short!("stream"), symbol_short!("created")).into_val(&env);
+
+    let stream_ids = client.create_batch_streams(&sender, &token_id, &requests);
+    let batches = find_events(&env, &client.address, batch_topics.clone());
+    assert_eq!(batches.len(), 1);
+    let (first, last, count): (u64, u64, u32) = batches.get(0).unwrap().into_val(&env);
+    assert_eq!(first, stream_ids.get(0).unwrap());
+    assert_eq!(last, stream_ids.last().unwrap());
+    assert_eq!(count, stream_ids.len());
+    assert_eq!(
+        find_events(&env, &client.address, created_topics.clone()).len(),
+        3
+    );
+
+    // Summary-only mode drops the per-stream events but keeps the range
+    client.set_batch_summary_only(&admin, &true);
+    let requests = vec![&env, request.clone(), request];
+    let stream_ids = client.create_batch_streams(&sender, &token_id, &requests);
+    assert_eq!(stream_ids, vec![&env, 3, 4]);
+
+    let batches = find_events(&env, &client.address, batch_topics);
+    assert_eq!(batches.len(), 1);
+    let (first, last, count): (u64, u64, u32) = batches.get(0).unwrap().into_val(&env);
+    assert_eq!((first, last, count), (3, 4, 2));
+    assert!(find_events(&env, &client.address, created_topics).is_empty());
+}
@@ -0,0 +1,317 @@
+#![cfg(test)]
+
+use crate::{errors::Error, test_helpers::setup_with_balance};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address,
+};
+
+#[test]
+fn test_no_fee_by_default() {
+    let ctx = setup_with_balance(100_000);
+    let stream_id = ctx.create_stream(10_000);
+
+    assert_eq!(ctx.client.get_stream(&stream_id).total_amount, 10_000);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 10_000);
+}
+
+#[test]
+fn test_fee_goes_to_single_treasury() {
+    let ctx = setup_with_balance(100_000);
+    let treasury = Address::generate(&ctx.env);
+    ctx.client.initialize_fee(&ctx.admin, &100, &treasury);
+
+    let stream_id = ctx.create_stream(10_000);
+
+    assert_eq!(ctx.client.get_stream(&stream_id).total_amount, 9_900);
+    assert_eq!(ctx.token.balance(&treasury), 100);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 9_900);
+}
+
+#[test]
+fn test_fee_split_70_30() {
+    let ctx = setup_with_balance(100_000);
+    let treasury = Address::generate(&ctx.env);
+    let staking = Address::generate(&ctx.env);
+    ctx.client
+        .initialize_fee(&ctx.admin, &100, &Address::generate(&ctx.env));
+    ctx.client.set_treasury_splits(
+        &ctx.admin,
+        &vec![
+            &ctx.env,
+            (treasury.clone(), 7_000),
+            (staking.clone(), 3_000),
+        ],
+    );
+
+    let stream_id = ctx.create_stream(10_000);
+
+    assert_eq!(ctx.client.get_stream(&stream_id).total_amount, 9_900);
+    assert_eq!(ctx.token.balance(&treasury), 70);
+    assert_eq!(ctx.token.balance(&staking), 30);
+}
+
+#[test]
+fn test_fee_split_remainder_goes_to_last_recipient() {
+    let ctx = setup_with_balance(100_000);
+    let first = Address::generate(&ctx.env);
+    let second = Address::generate(&ctx.env);
+    ctx.client
+        .initialize_fee(&ctx.admin, &100, &Address::generate(&ctx.env));
+    ctx.client.set_treasury_splits(
+        &ctx.admin,
+        &vec![&ctx.env, (first.clone(), 7_000), (second.clone(), 3_000)],
+    );
+
+    // Fee is 33: 70% truncates to 23, the last split takes the remaining 10
+    ctx.create_stream(3_300);
+
+    assert_eq!(ctx.token.balance(&first), 23);
+    assert_eq!(ctx.token.balance(&second), 10);
+}
+
+#[test]
+fn test_fee_split_shares_sum_to_fee_for_large_amounts() {
+    let ctx = setup_with_balance(100_000);
+    let recipients = [0; 3].map(|_| Address::generate(&ctx.env));
+    ctx.client
+        .initialize_fee(&ctx.admin, &1_000, &Address::generate(&ctx.env));
+    ctx.client.set_treasury_splits(
+        &ctx.admin,
+        &vec![
+            &ctx.env,
+            (recipients[0].clone(), 3_333),
+            (recipients[1].clone(), 3_333),
+            (recipients[2].clone(), 3_334),
+        ],
+    );
+
+    // A fee this large overflows `fee * bps` if the split is not done on the
+    // 10_000 denominator first
+    let amount = 10i128.pow(37) + 7;
+    StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&ctx.sender, &amount);
+    let stream_id = ctx.create_stream(amount);
+
+    let fee = amount - ctx.client.get_stream(&stream_id).total_amount;
+    let shares: i128 = recipients.iter().map(|r| ctx.token.balance(r)).sum();
+    assert_eq!(shares, fee);
+    assert_eq!(ctx.token.balance(&recipients[0]), fee / 10_000 * 3_333);
+}
+
+#[test]
+fn test_treasury_splits_validation() {
+    let ctx = setup_with_balance(100_000);
+    let a = Address::generate(&ctx.env);
+    let b = Address::generate(&ctx.env);
+
+    let result = ctx
+        .client
+        .try_set_treasury_splits(&ctx.admin, &vec![&ctx.env]);
+    assert_eq!(result, Err(Ok(Error::InvalidTreasurySplits)));
+
+    let result = ctx.client.try_set_treasury_splits(
+        &ctx.admin,
+        &vec![&ctx.env, (a.clone(), 7_000), (b.clone(), 2_000)],
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidTreasurySplits)));
+
+    let result = ctx
+        .client
+        .try_set_treasury_splits(&ctx.sender, &vec![&ctx.env, (a, 10_000)]);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_fee_cap_enforced() {
+    let ctx = setup_with_balance(100_000);
+    let treasury = Address::generate(&ctx.env);
+
+    let result = ctx.client.try_initialize_fee(&ctx.admin, &1_001, &treasury);
+    assert_eq!(result, Err(Ok(Error::FeeTooHigh)));
+
+    ctx.client.initialize_fee(&ctx.admin, &1_000, &treasury);
+    let result = ctx.client.try_update_fee(&ctx.admin, &1_001);
+    assert_eq!(result, Err(Ok(Error::FeeTooHigh)));
+}
+
+#[test]
+fn test_fee_charged_in_fee_token() {
+    let ctx = setup_with_balance(100_000);
+    let treasury = Address::generate(&ctx.env);
+    let fee_token_id = ctx
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&ctx.env))
+        .address();
+    let fee_token = TokenClient::new(&ctx.env, &fee_token_id);
+    StellarAssetClient::new(&ctx.env, &fee_token_id).mint(&ctx.sender, &1_000);
+
+    ctx.client.initialize_fee(&ctx.admin, &100, &treasury);
+    ctx.client
+        .set_fee_token(&ctx.admin, &Some(fee_token_id.clone()));
+
+    let stream_id = ctx.create_stream(10_000);
+
+    // Full amount streams; the 1% fee is paid in the fee token
+    assert_eq!(ctx.client.get_stream(&stream_id).total_amount, 10_000);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 10_000);
+    assert_eq!(ctx.token.balance(&treasury), 0);
+    assert_eq!(fee_token.balance(&treasury), 100);
+    assert_eq!(fee_token.balance(&ctx.sender), 900);
+
+    // Clearing the fee token goes back to skimming the streamed asset
+    ctx.client.set_fee_token(&ctx.admin, &None);
+    let stream_id = ctx.create_stream(10_000);
+    assert_eq!(ctx.client.get_stream(&stream_id).total_amount, 9_900);
+    assert_eq!(ctx.token.balance(&treasury), 100);
+}
+
+#[test]
+fn test_set_fee_token_requires_treasury_manager() {
+    let ctx = setup_with_balance(100_000);
+    let result = ctx
+        .client
+        .try_set_fee_token(&ctx.sender, &Some(ctx.token_id.clone()));
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_effective_fee_global_and_token_override() {
+    let ctx = setup_with_balance(100_000);
+    let treasury = Address::generate(&ctx.env);
+    let other_token = Address::generate(&ctx.env);
+    ctx.client.initialize_fee(&ctx.admin, &100, &treasury);
+
+    assert_eq!(ctx.client.get_effective_fee_bps(&ctx.token_id, &0), 100);
+
+    ctx.client
+        .set_token_fee(&ctx.admin, &ctx.token_id, &Some(25));
+    assert_eq!(ctx.client.get_effective_fee_bps(&ctx.token_id, &0), 25);
+    assert_eq!(ctx.client.get_effective_fee_bps(&other_token, &0), 100);
+
+    // Creation charges the override
+    let stream_id = ctx.create_stream(10_000);
+    assert_eq!(ctx.client.get_stream(&stream_id).total_amount, 9_975);
+    assert_eq!(ctx.token.balance(&treasury), 25);
+
+    ctx.client.set_token_fee(&ctx.admin, &ctx.token_id, &None);
+    assert_eq!(ctx.client.get_effective_fee_bps(&ctx.token_id, &0), 100);
+
+    let result = ctx
+        .client
+        .try_set_token_fee(&ctx.admin, &ctx.token_id, &Some(1001));
+    assert_eq!(result, Err(Ok(Error::FeeTooHigh)));
+}
+
+#[test]
+fn test_scheduled_fee_change_applies_at_effective_time() {
+    let ctx = setup_with_balance(100_000);
+    let treasury = Address::generate(&ctx.env);
+    ctx.client.initialize_fee(&ctx.admin, &100, &treasury);
+    ctx.client.schedule_fee_change(&ctx.admin, &300, &500);
+
+    assert_eq!(ctx.client.get_effective_fee_bps(&ctx.token_id, &499), 100);
+    assert_eq!(ctx.client.get_effective_fee_bps(&ctx.token_id, &500), 300);
+    assert_eq!(ctx.client.get_fee_bps(), 100);
+
+    // Before the change takes effect the old fee is charged
+    let before = ctx.create_stream(10_000);
+    assert_eq!(ctx.client.get_stream(&before).total_amount, 9_900);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(ctx.client.get_fee_bps(), 300);
+    let after = ctx.create_stream(10_000);
+    assert_eq!(ctx.client.get_stream(&after).total_amount, 9_700);
+
+    // A token override still wins over the scheduled global fee
+    ctx.client
+        .set_token_fee(&ctx.admin, &ctx.token_id, &Some(50));
+    assert_eq!(ctx.client.get_effective_fee_bps(&ctx.token_id, &600), 50);
+}
+
+#[test]
+fn test_update_fee_after_scheduled_change_took_effect() {
+    let ctx = setup_with_balance(100_000);
+    let treasury = Address::generate(&ctx.env);
+    ctx.client.initialize_fee(&ctx.admin, &100, &treasury);
+    ctx.client.schedule_fee_change(&ctx.admin, &300, &500);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 600);
+    assert_eq!(ctx.client.get_fee_bps(), 300);
+
+    ctx.client.update_fee(&ctx.admin, &50);
+    assert_eq!(ctx.client.get_fee_bps(), 50);
+    let stream_id = ctx.create_stream(10_000);
+    assert_eq!(ctx.client.get_stream(&stream_id).total_amount, 9_950);
+
+    // A later schedule starts from the updated fee, not the old scheduled one
+    ctx.client.schedule_fee_change(&ctx.admin, &200, &900);
+    assert_eq!(ctx.client.get_fee_bps(), 50);
+    ctx.env.ledger().with_mut(|li| li.timestamp = 900);
+    assert_eq!(ctx.client.get_fee_bps(), 200);
+}
+
+#[test]
+fn test_schedule_fee_change_validation() {
+    let ctx = setup_with_balance(100_000);
+    ctx.env.ledger().with_mut(|li| li.timestamp = 100);
+
+    let result = ctx.client.try_schedule_fee_change(&ctx.admin, &100, &100);
+    assert_eq!(result, Err(Ok(Error::InvalidTimeRange)));
+
+    let result = ctx.client.try_schedule_fee_change(&ctx.admin, &1001, &200);
+    assert_eq!(result, Err(Ok(Error::FeeTooHigh)));
+
+    let result = ctx.client.try_schedule_fee_change(&ctx.sender, &100, &200);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_quote_matches_create_stream() {
+    let ctx = setup_with_balance(100_000);
+    let treasury = Address::generate(&ctx.env);
+    ctx.client.initialize_fee(&ctx.admin, &50, &treasury);
+    ctx.client
+        .set_token_fee(&ctx.admin, &ctx.token_id, &Some(33));
+
+    for amount in [1000_i128, 12_345, 7] {
+        let (principal, fee, quoted_treasury) = ctx.client.quote_stream(&ctx.token_id, &amount);
+        assert_eq!(principal + fee, amount);
+        assert_eq!(quoted_treasury, treasury);
+
+        let treasury_before = ctx.token.balance(&treasury);
+        let sender_before = ctx.token.balance(&ctx.sender);
+        let stream_id = ctx.create_stream(amount);
+
+        assert_eq!(ctx.client.get_stream(&stream_id).total_amount, principal);
+        assert_eq!(ctx.token.balance(&treasury) - treasury_before, fee);
+        assert_eq!(sender_before - ctx.token.balance(&ctx.sender), amount);
+    }
+}
+
+#[test]
+fn test_quote_without_treasury_returns_contract() {
+    let ctx = setup_with_balance(100_000);
+
+    let (principal, fee, treasury) = ctx.client.quote_stream(&ctx.token_id, &1000);
+    assert_eq!((principal, fee), (1000, 0));
+    assert_eq!(treasury, ctx.client.address);
+
+    let stream_id = ctx.create_stream(1000);
+    assert_eq!(ctx.client.get_stream(&stream_id).total_amount, 1000);
+}
+
+#[test]
+fn test_quote_changes_nothing() {
+    let ctx = setup_with_balance(100_000);
+    let treasury = Address::generate(&ctx.env);
+    ctx.client.initialize_fee(&ctx.admin, &100, &treasury);
+
+    assert_eq!(
+        ctx.client.quote_stream(&ctx.token_id, &1000),
+        (990, 10, treasury.clone())
+    );
+    assert_eq!(ctx.token.balance(&ctx.sender), 100_000);
+    assert_eq!(ctx.token.balance(&treasury), 0);
+    assert!(!ctx.client.stream_exists(&0));
+}
@@ -0,0 +1,66 @@
+#![cfg(test)]
+
+use crate::{errors::Error, test_helpers::setup};
+use soroban_sdk::testutils::Ledger;
+
+#[test]
+fn test_finalize_pays_out_partially_claimed_stream() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 300);
+    ctx.client.withdraw(&stream_id, &ctx.receiver);
+
+    // Keeper settles long after the end, with no auth from anyone
+    ctx.env.set_auths(&[]);
+    ctx.env.ledger().with_mut(|li| li.timestamp = 5000);
+    assert_eq!(ctx.client.finalize_stream(&stream_id), 700);
+
+    assert_eq!(ctx.token.balance(&ctx.receiver), 1000);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 0);
+    let stream = ctx.client.get_stream(&stream_id);
+    assert_eq!(stream.withdrawn_amount, stream.total_amount);
+
+    // Nothing left to settle the second time
+    assert_eq!(
+        ctx.client.try_finalize_stream(&stream_id),
+        Err(Ok(Error::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_finalize_rejects_ongoing_stream() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 999);
+    assert_eq!(
+        ctx.client.try_finalize_stream(&stream_id),
+        Err(Ok(Error::StreamNotEnded))
+    );
+    assert_eq!(ctx.token.balance(&ctx.receiver), 0);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(ctx.client.finalize_stream(&stream_id), 1000);
+}
+
+#[test]
+fn test_finalize_waits_for_pause_adjusted_end() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+    let sender = ctx.client.get_stream(&stream_id).sender;
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 400);
+    ctx.client.pause_stream(&stream_id, &sender);
+    ctx.env.ledger().with_mut(|li| li.timestamp = 600);
+    ctx.client.unpause_stream(&stream_id, &sender);
+
+    // The 200s pause moved the end to 1200
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1100);
+    assert_eq!(
+        ctx.client.try_finalize_stream(&stream_id),
+        Err(Ok(Error::StreamNotEnded))
+    );
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1200);
+    assert_eq!(ctx.client.finalize_stream(&stream_id), 1000);
+}
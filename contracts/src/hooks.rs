@@ -0,0 +1,10 @@
+use soroban_sdk::{contractclient, Env};
+
+/// Callback interface for receiver contracts that opted into `on_cancel_hook`
+#[allow(dead_code)]
+#[contractclient(name = "CancelHookClient")]
+pub trait CancelHook {
+    /// Called after a cancelled stream has settled. `amount` is what the receiver
+    /// was paid on cancellation.
+    fn on_stream_cancelled(env: Env, stream_id: u64, amount: i128);
+}
@@ -0,0 +1,154 @@
+#![cfg(test)]
+
+use crate::{
+    test_helpers::{setup, TestContext},
+    types::{CurveType, StreamOptions},
+};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env,
+};
+
+fn keyed(env: &Env, byte: u8) -> StreamOptions {
+    StreamOptions {
+        is_soulbound: false,
+        idempotency_key: Some(BytesN::from_array(env, &[byte; 32])),
+        mutual_cancel: false,
+        revert_after: None,
+        on_cancel_hook: false,
+    }
+}
+
+#[test]
+fn test_duplicate_key_returns_existing_stream() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        token,
+        ..
+    } = setup();
+
+    let first = client.create_stream_with_options(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &keyed(&env, 7),
+    );
+    assert_eq!(token.balance(&sender), 9000);
+
+    // Relayer retry with the same key
+    let retry = client.create_stream_with_options(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &keyed(&env, 7),
+    );
+    assert_eq!(retry, first);
+    assert_eq!(token.balance(&sender), 9000);
+
+    // A fresh key creates a new stream
+    let second = client.create_stream_with_options(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &keyed(&env, 8),
+    );
+    assert_ne!(second, first);
+    assert_eq!(token.balance(&sender), 8000);
+}
+
+#[test]
+fn test_no_key_always_creates() {
+    let TestContext {
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let options = StreamOptions {
+        is_soulbound: false,
+        idempotency_key: None,
+        mutual_cancel: false,
+        revert_after: None,
+        on_cancel_hook: false,
+    };
+
+    let first = client.create_stream_with_options(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &options,
+    );
+    let second = client.create_stream_with_options(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &options,
+    );
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_keys_are_scoped_to_the_sender() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let other = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&other, &1000);
+
+    let first = client.create_stream_with_options(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &keyed(&env, 1),
+    );
+
+    // Another sender can't claim the key: reusing it just creates their own stream
+    let second = client.create_stream_with_options(
+        &other,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &keyed(&env, 1),
+    );
+    assert_ne!(second, first);
+    assert_eq!(client.get_stream(&second).sender, other);
+    assert_eq!(TokenClient::new(&env, &token_id).balance(&other), 0);
+}
@@ -3,6 +3,7 @@
 
 mod errors;
 mod flash_loan;
+mod hooks;
 mod interest;
 mod math;
 mod oracle;
@@ -46,15 +47,129 @@ mod voting_test;
 #[cfg(test)]
 mod ttl_stress_test;
 
+#[cfg(test)]
+mod rate_limit_test;
+
+#[cfg(test)]
+mod stream_balance_test;
+
+#[cfg(test)]
+mod fee_test;
+
+#[cfg(test)]
+mod partial_withdraw_test;
+
+#[cfg(test)]
+mod events_test;
+
+#[cfg(test)]
+mod pause_test;
+
+#[cfg(test)]
+mod validation_test;
+
+#[cfg(test)]
+mod idempotency_test;
+
+#[cfg(test)]
+mod allowance_test;
+#[cfg(test)]
+mod batch_test;
+#[cfg(test)]
+mod cancel_grace_test;
+#[cfg(test)]
+mod cancel_hook_test;
+#[cfg(test)]
+mod custom_curve_test;
+#[cfg(test)]
+mod default_curve_test;
+#[cfg(test)]
+mod finalize_test;
+#[cfg(test)]
+mod migrate_test;
+#[cfg(test)]
+mod mutual_cancel_test;
+#[cfg(test)]
+mod rbac_test;
+#[cfg(test)]
+mod reentrancy_test;
+#[cfg(test)]
+mod revert_test;
+#[cfg(test)]
+mod role_admin_test;
+#[cfg(test)]
+mod schedule_test;
+#[cfg(test)]
+mod self_stream_test;
+#[cfg(test)]
+mod short_stream_test;
+#[cfg(test)]
+mod stream_batch_read_test;
+#[cfg(test)]
+mod stream_pause_test;
+#[cfg(test)]
+mod stream_rate_test;
+#[cfg(test)]
+mod stream_totals_test;
+#[cfg(test)]
+mod subscription_test;
+#[cfg(test)]
+mod test_helpers;
+#[cfg(test)]
+mod token_index_test;
+#[cfg(test)]
+mod upgrade_timelock_test;
+#[cfg(test)]
+mod withdraw_all_test;
+
 use errors::Error;
-use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env, Vec};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, token, Address, Env, Map, Symbol, TryFromVal, Val, Vec,
+};
 use storage::{PROPOSAL_COUNT, RECEIPT, RESTRICTED_ADDRESSES, STREAM_COUNT};
 use types::{
-    ContributorRequest, CurveType, DataKey, Milestone, ProposalApprovedEvent, ProposalCreatedEvent,
+    ContributorRequest, CurveParams, CurveType, DataKey, Governance, Milestone,
+    MultiTokenStreamRequest, PendingUpgrade, ProposalApprovedEvent, ProposalCreatedEvent,
     ReceiptMetadata, RequestCreatedEvent, RequestExecutedEvent, RequestKey, RequestStatus, Role,
-    Stream, StreamCreatedEvent, StreamProposal, StreamReceipt,
+    Stream, StreamIndex, StreamOptions, StreamProposal, StreamReceipt, StreamRequest, StreamV1,
 };
 
+/// Maximum protocol fee: 10% (1000 basis points).
+const MAX_FEE_BPS: u32 = 1000;
+
+/// Upper bound on points returned by `get_unlock_schedule`.
+const MAX_SCHEDULE_SAMPLES: u32 = 100;
+
+/// Upper bound on ids accepted by `get_streams_batch`.
+const MAX_BATCH_READ: u32 = 50;
+
+/// Default upper bound on requests in one batch creation call. A stream to a new
+/// receiver touches about four ledger entries (two for the receiver's index), so
+/// this keeps a batch well under the per-invocation footprint and write limits.
+const MAX_BATCH_SIZE: u32 = 16;
+
+/// Stream ids per index bucket: ids `b * 64..(b + 1) * 64` share bucket `b`.
+const INDEX_BUCKET_SIZE: u64 = 64;
+
+/// TTL given to instance storage and index entries on write, ~1 year at 5s/ledger.
+const EXTEND_LEDGERS: u32 = 6_000_000;
+
+/// Upper bound on streams visited by one `withdraw_all` call.
+const MAX_WITHDRAW_ALL: u32 = 20;
+
+/// Delay between `propose_upgrade` and the earliest time it can execute.
+const UPGRADE_DELAY_SECS: u64 = 172_800;
+
+/// Length of the bucket used to rate-limit stream creation per sender.
+const CREATION_WINDOW_SECS: u64 = 3_600;
+
+/// Upper bound on checkpoints in a `CurveType::Custom` schedule.
+const MAX_CURVE_CHECKPOINTS: u32 = 16;
+
+/// Storage layout version written by `initialize` and brought up to date by `migrate`.
+/// Deployments from before versioning report 1.
+const CONTRACT_VERSION: u32 = 3;
+
 #[contract]
 pub struct StellarStreamContract;
 
@@ -181,6 +296,21 @@ impl StellarStreamContract {
     }
 
     fn execute_proposal(env: &Env, proposal: StreamProposal) -> Result<u64, Error> {
+        // Proposals use the configured default curve and are never soulbound
+        let (default_type, default_params) = Self::default_curve(env);
+        let stream = Self::build_stream(
+            env,
+            &proposal.sender,
+            &proposal.receiver,
+            &proposal.token,
+            proposal.total_amount,
+            proposal.start_time,
+            proposal.end_time,
+            Vec::new(env),
+            default_type,
+            default_params,
+        )?;
+
         // Transfer tokens from proposer to contract
         let token_client = token::Client::new(env, &proposal.token);
         token_client.transfer(
@@ -193,57 +323,15 @@ impl StellarStreamContract {
         let stream_id: u64 = env.storage().instance().get(&STREAM_COUNT).unwrap_or(0);
         let next_id = stream_id + 1;
 
-        let stream = Stream {
-            sender: proposal.sender.clone(),
-            receiver: proposal.receiver.clone(),
-            token: proposal.token.clone(),
-            total_amount: proposal.total_amount,
-            start_time: proposal.start_time,
-            end_time: proposal.end_time,
-            withdrawn_amount: 0,
-            interest_strategy: 0,
-            vault_address: None,
-            deposited_principal: proposal.total_amount,
-            metadata: None,
-            withdrawn: 0,
-            cancelled: false,
-            receipt_owner: proposal.receiver.clone(),
-            is_paused: false,
-            paused_time: 0,
-            total_paused_duration: 0,
-            milestones: Vec::new(env),
-            curve_type: CurveType::Linear,
-            is_usd_pegged: false,
-            usd_amount: 0,
-            oracle_address: proposal.sender.clone(),
-            oracle_max_staleness: 0,
-            price_min: 0,
-            price_max: 0,
-            is_soulbound: false,     // Proposals default to non-soulbound
-            clawback_enabled: false, // Check at runtime if needed
-            arbiter: None,
-            is_frozen: false,
-        };
-
         env.storage()
             .instance()
             .set(&(STREAM_COUNT, stream_id), &stream);
         env.storage().instance().set(&STREAM_COUNT, &next_id);
+        Self::adjust_liability(env, &stream, stream.total_amount);
 
-        // Emit StreamCreatedEvent
-        env.events().publish(
-            (symbol_short!("create"), proposal.sender.clone()),
-            StreamCreatedEvent {
-                stream_id,
-                sender: proposal.sender.clone(),
-                receiver: proposal.receiver.clone(),
-                token: proposal.token,
-                total_amount: proposal.total_amount,
-                start_time: proposal.start_time,
-                end_time: proposal.end_time,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+        Self::index_stream(env, stream_id, &stream);
+        let decimals = Self::cache_token_decimals(env, &stream.token);
+        Self::emit_stream_created(env, stream_id, &stream, decimals);
         Self::mint_receipt(env, stream_id, &proposal.receiver);
 
         Ok(stream_id)
@@ -298,70 +386,181 @@ impl StellarStreamContract {
         curve_type: CurveType,
         is_soulbound: bool,
         vault_address: Option<Address>,
+    ) -> Result<u64, Error> {
+        let curve_params = Self::no_curve_params(&env);
+        Self::create_stream_internal(
+            env,
+            sender,
+            receiver,
+            token,
+            total_amount,
+            start_time,
+            end_time,
+            milestones,
+            curve_type,
+            curve_params,
+            vault_address,
+            StreamOptions {
+                is_soulbound,
+                idempotency_key: None,
+                mutual_cancel: false,
+                revert_after: None,
+                on_cancel_hook: false,
+            },
+        )
+    }
+
+    /// Create a new stream with optional creation settings
+    ///
+    /// # Parameters
+    /// - `options.idempotency_key`: If set and already used by this sender, the existing
+    ///   stream id is returned and no tokens move. Lets relayers retry safely.
+    /// - `options.mutual_cancel`: If true, `cancel` needs both sender and receiver to sign.
+    /// - `options.revert_after`: If set, the sender may reclaim unwithdrawn funds from this
+    ///   time on with `revert_unclaimed`. Must not be before `end_time`, and top-ups
+    ///   that would move the end past it are rejected.
+    /// - `options.on_cancel_hook`: If true, `cancel` calls `on_stream_cancelled` on the
+    ///   receiver contract. The call is best effort: if it fails, the cancel still completes.
+    pub fn create_stream_with_options(
+        env: Env,
+        sender: Address,
+        receiver: Address,
+        token: Address,
+        total_amount: i128,
+        start_time: u64,
+        end_time: u64,
+        curve_type: CurveType,
+        options: StreamOptions,
+    ) -> Result<u64, Error> {
+        let milestones = Vec::new(&env);
+        let curve_params = Self::no_curve_params(&env);
+        Self::create_stream_internal(
+            env,
+            sender,
+            receiver,
+            token,
+            total_amount,
+            start_time,
+            end_time,
+            milestones,
+            curve_type,
+            curve_params,
+            None,
+            options,
+        )
+    }
+
+    /// Create a stream on a curve shaped by `curve_params`: `CurveType::Custom` reads
+    /// `checkpoints` and `CurveType::Step` reads `step_interval`. Other curves ignore
+    /// the params, so this behaves like `create_stream` for them.
+    pub fn create_stream_with_curve(
+        env: Env,
+        sender: Address,
+        receiver: Address,
+        token: Address,
+        total_amount: i128,
+        start_time: u64,
+        end_time: u64,
+        curve_type: CurveType,
+        curve_params: CurveParams,
+    ) -> Result<u64, Error> {
+        let milestones = Vec::new(&env);
+        Self::create_stream_internal(
+            env,
+            sender,
+            receiver,
+            token,
+            total_amount,
+            start_time,
+            end_time,
+            milestones,
+            curve_type,
+            curve_params,
+            None,
+            StreamOptions {
+                is_soulbound: false,
+                idempotency_key: None,
+                mutual_cancel: false,
+                revert_after: None,
+                on_cancel_hook: false,
+            },
+        )
+    }
+
+    fn create_stream_internal(
+        env: Env,
+        sender: Address,
+        receiver: Address,
+        token: Address,
+        total_amount: i128,
+        start_time: u64,
+        end_time: u64,
+        milestones: Vec<Milestone>,
+        curve_type: CurveType,
+        curve_params: CurveParams,
+        vault_address: Option<Address>,
+        options: StreamOptions,
     ) -> Result<u64, Error> {
         sender.require_auth();
+        Self::check_not_paused(&env)?;
 
-        // Validate time range
-        if start_time >= end_time {
-            return Err(Error::InvalidTimeRange);
+        // A retried submission returns the stream it already created. Keys are
+        // scoped to the sender, so nobody else can claim one first.
+        if let Some(ref key) = options.idempotency_key {
+            let existing: Option<u64> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::IdempotencyKey(sender.clone(), key.clone()));
+            if let Some(stream_id) = existing {
+                return Ok(stream_id);
+            }
         }
-        if total_amount <= 0 {
-            return Err(Error::InvalidAmount);
+
+        let mut stream = Self::build_stream(
+            &env,
+            &sender,
+            &receiver,
+            &token,
+            total_amount,
+            start_time,
+            end_time,
+            milestones,
+            curve_type,
+            curve_params,
+        )?;
+        stream.is_soulbound = options.is_soulbound;
+        stream.mutual_cancel = options.mutual_cancel;
+        if let Some(revert_after) = options.revert_after {
+            if revert_after < end_time {
+                return Err(Error::InvalidTimeRange);
+            }
         }
+        stream.revert_after = options.revert_after;
+        stream.on_cancel_hook = options.on_cancel_hook;
+        stream.vault_address = vault_address.clone();
+
         if Self::is_address_restricted(env.clone(), receiver.clone()) {
             soroban_sdk::panic_with_error!(&env, Error::AddressRestricted);
         }
+        Self::record_stream_creation(&env, &sender);
 
-        // Validate vault if provided
-        let vault_shares = if let Some(ref vault) = vault_address {
-            // Transfer tokens to contract first
-            let token_client = token::Client::new(&env, &token);
-            token_client.transfer(&sender, &env.current_contract_address(), &total_amount);
+        let (principal, fee_amount) = Self::creation_fee(&env, &token, total_amount);
+        Self::collect_deposit(&env, &sender, &token, total_amount, fee_amount);
 
-            // Deposit to vault and get shares
-            vault::deposit_to_vault(&env, vault, &token, total_amount)
+        // Deposit to vault if provided and get shares
+        let vault_shares = if let Some(ref vault) = vault_address {
+            vault::deposit_to_vault(&env, vault, &token, principal)
                 .map_err(|_| Error::InvalidAmount)?
         } else {
             // Standard stream without vault
-            let token_client = token::Client::new(&env, &token);
-            token_client.transfer(&sender, &env.current_contract_address(), &total_amount);
             0
         };
 
         let stream_id: u64 = env.storage().instance().get(&STREAM_COUNT).unwrap_or(0);
         let next_id = stream_id + 1;
 
-        let stream = Stream {
-            sender: sender.clone(),
-            receiver: receiver.clone(),
-            token: token.clone(),
-            total_amount,
-            start_time,
-            end_time,
-            withdrawn_amount: 0,
-            interest_strategy: 0,
-            vault_address: vault_address.clone(),
-            deposited_principal: total_amount,
-            metadata: None,
-            withdrawn: 0,
-            cancelled: false,
-            receipt_owner: receiver.clone(),
-            is_paused: false,
-            paused_time: 0,
-            total_paused_duration: 0,
-            milestones,
-            curve_type,
-            is_usd_pegged: false,
-            usd_amount: 0,
-            oracle_address: sender.clone(),
-            oracle_max_staleness: 0,
-            price_min: 0,
-            price_max: 0,
-            is_soulbound,
-            clawback_enabled: false, // TODO: Check token flags
-            arbiter: None,
-            is_frozen: false,
-        };
+        stream.total_amount = principal;
+        stream.deposited_principal = principal;
 
         let stream_key = (STREAM_COUNT, stream_id);
 
@@ -370,6 +569,7 @@ impl StellarStreamContract {
 
         env.storage().instance().set(&stream_key, &stream);
         env.storage().instance().set(&STREAM_COUNT, &next_id);
+        Self::adjust_liability(&env, &stream, stream.total_amount);
 
         // Store vault shares if vault is used
         if vault_shares > 0 {
@@ -378,8 +578,15 @@ impl StellarStreamContract {
                 .set(&DataKey::VaultShares(stream_id), &vault_shares);
         }
 
+        if let Some(ref key) = options.idempotency_key {
+            env.storage().persistent().set(
+                &DataKey::IdempotencyKey(sender.clone(), key.clone()),
+                &stream_id,
+            );
+        }
+
         // If soulbound, emit event and add to index
-        if is_soulbound {
+        if options.is_soulbound {
             env.events().publish(
                 (symbol_short!("soulbound"), symbol_short!("locked")),
                 (stream_id, receiver.clone()),
@@ -397,79 +604,596 @@ impl StellarStreamContract {
                 .set(&DataKey::SoulboundStreams, &soulbound_streams);
         }
 
-        env.events().publish(
-            (symbol_short!("create"), sender.clone()),
-            StreamCreatedEvent {
-                stream_id,
-                sender: sender.clone(),
-                receiver: receiver.clone(),
-                token,
-                total_amount,
-                start_time,
-                end_time,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+        Self::index_stream(&env, stream_id, &stream);
+        let decimals = Self::cache_token_decimals(&env, &stream.token);
+        Self::emit_stream_created(&env, stream_id, &stream, decimals);
         Self::mint_receipt(&env, stream_id, &receiver);
 
         Ok(stream_id)
     }
 
-    pub fn initialize(env: Env, admin: Address) {
-        admin.require_auth();
-
-        // Set admin role
-        env.storage().instance().set(&DataKey::Admin, &admin);
+    /// Create one stream per request, all funded in `token` by a single transfer.
+    ///
+    /// Every request is validated before any funds move. Fees are charged per stream
+    /// exactly as in `create_stream`. Streams accrue from `start_time`, so a request
+    /// whose `cliff_time` falls after it is rejected. Batches larger than
+    /// `get_max_batch_size` panic with "Batch too large" before anything is checked.
+    pub fn create_batch_streams(
+        env: Env,
+        sender: Address,
+        token: Address,
+        requests: Vec<StreamRequest>,
+    ) -> Result<Vec<u64>, Error> {
+        sender.require_auth();
+        Self::check_not_paused(&env)?;
+        Self::check_batch_size(&env, requests.len());
+
+        let mut streams: Vec<Stream> = Vec::new(&env);
+        let mut total_deposit: i128 = 0;
+        let mut total_fee: i128 = 0;
+        for request in requests.iter() {
+            let (stream, fee_amount) = Self::build_batch_stream(&env, &sender, &token, &request)?;
+            total_deposit = total_deposit
+                .checked_add(request.amount)
+                .ok_or(Error::InvalidAmount)?;
+            total_fee += fee_amount;
+            streams.push_back(stream);
+        }
 
-        // Grant all roles to admin
-        env.storage()
-            .instance()
-            .set(&DataKey::Role(admin.clone(), Role::Admin), &true);
-        env.storage()
-            .instance()
-            .set(&DataKey::Role(admin.clone(), Role::Pauser), &true);
-        env.storage()
-            .instance()
-            .set(&DataKey::Role(admin.clone(), Role::TreasuryManager), &true);
+        Self::collect_deposit(&env, &sender, &token, total_deposit, total_fee);
+        Self::store_batch_streams(&env, streams)
     }
 
-    // ========== RBAC Functions ==========
-
-    /// Grant a role to an address (Admin only)
-    pub fn grant_role(env: Env, admin: Address, target: Address, role: Role) {
-        admin.require_auth();
-
-        // Check if caller has Admin role
-        if !Self::has_role(&env, &admin, Role::Admin) {
-            panic!("{}", Error::Unauthorized as u32);
+    /// Like `create_batch_streams`, but each request names its own token. Deposits and
+    /// fees are totalled per token and collected with one transfer per token.
+    pub fn create_multi_token_batch(
+        env: Env,
+        sender: Address,
+        requests: Vec<MultiTokenStreamRequest>,
+    ) -> Result<Vec<u64>, Error> {
+        sender.require_auth();
+        Self::check_not_paused(&env)?;
+        Self::check_batch_size(&env, requests.len());
+
+        let mut streams: Vec<Stream> = Vec::new(&env);
+        // token -> (deposit, fee)
+        let mut totals: Map<Address, (i128, i128)> = Map::new(&env);
+        for item in requests.iter() {
+            let (stream, fee_amount) =
+                Self::build_batch_stream(&env, &sender, &item.token, &item.request)?;
+            let (deposit, fee) = totals.get(item.token.clone()).unwrap_or((0, 0));
+            let deposit = deposit
+                .checked_add(item.request.amount)
+                .ok_or(Error::InvalidAmount)?;
+            totals.set(item.token, (deposit, fee + fee_amount));
+            streams.push_back(stream);
         }
 
-        // Grant the role
-        env.storage()
-            .instance()
-            .set(&DataKey::Role(target.clone(), role.clone()), &true);
+        for (token, (deposit, fee)) in totals.iter() {
+            Self::collect_deposit(&env, &sender, &token, deposit, fee);
+        }
+        Self::store_batch_streams(&env, streams)
+    }
 
-        // Emit event
-        env.events().publish((symbol_short!("grant"), target), role);
+    /// Cap batch creation at `get_max_batch_size` requests. Checked before any request
+    /// is built, so an oversized batch fails before funds move rather than mid-loop.
+    fn check_batch_size(env: &Env, len: u32) {
+        if len > Self::get_max_batch_size(env.clone()) {
+            panic!("Batch too large");
+        }
     }
 
-    /// Revoke a role from an address (Admin only)
-    pub fn revoke_role(env: Env, admin: Address, target: Address, role: Role) {
-        admin.require_auth();
+    /// Validate one batch request and build its stream, with the fee already skimmed.
+    /// Returns the stream and the fee owed for it.
+    fn build_batch_stream(
+        env: &Env,
+        sender: &Address,
+        token: &Address,
+        request: &StreamRequest,
+    ) -> Result<(Stream, i128), Error> {
+        if request.cliff_time > request.start_time {
+            return Err(Error::InvalidTimeRange);
+        }
+        let (curve_type, curve_params) = Self::default_curve(env);
+        let mut stream = Self::build_stream(
+            env,
+            sender,
+            &request.receiver,
+            token,
+            request.amount,
+            request.start_time,
+            request.end_time,
+            Vec::new(env),
+            curve_type,
+            curve_params,
+        )?;
+        if Self::is_address_restricted(env.clone(), request.receiver.clone()) {
+            soroban_sdk::panic_with_error!(env, Error::AddressRestricted);
+        }
+        Self::record_stream_creation(env, sender);
+
+        let (principal, fee_amount) = Self::creation_fee(env, token, request.amount);
+        stream.total_amount = principal;
+        stream.deposited_principal = principal;
+        stream.interest_strategy = request.interest_strategy;
+        stream.vault_address = request.vault_address.clone();
+        stream.metadata = request.metadata.clone();
+        Ok((stream, fee_amount))
+    }
 
-        // Check if caller has Admin role
+    /// Store already-funded batch streams under consecutive ids, returning the ids
+    fn store_batch_streams(env: &Env, streams: Vec<Stream>) -> Result<Vec<u64>, Error> {
+        let first_stream_id: u64 = env.storage().instance().get(&STREAM_COUNT).unwrap_or(0);
+        let summary_only: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::BatchSummaryOnly)
+            .unwrap_or(false);
+        let mut stream_id = first_stream_id;
+        let mut stream_ids = Vec::new(env);
+        for stream in streams.iter() {
+            if let Some(ref vault) = stream.vault_address {
+                let shares =
+                    vault::deposit_to_vault(env, vault, &stream.token, stream.total_amount)
+                        .map_err(|_| Error::InvalidAmount)?;
+                if shares > 0 {
+                    env.storage()
+                        .instance()
+                        .set(&DataKey::VaultShares(stream_id), &shares);
+                }
+            }
+
+            env.storage()
+                .instance()
+                .set(&(STREAM_COUNT, stream_id), &stream);
+            Self::adjust_liability(env, &stream, stream.total_amount);
+            Self::index_stream(env, stream_id, &stream);
+            if !summary_only {
+                let decimals = Self::cache_token_decimals(env, &stream.token);
+                Self::emit_stream_created(env, stream_id, &stream, decimals);
+            }
+            Self::mint_receipt(env, stream_id, &stream.receiver);
+
+            stream_ids.push_back(stream_id);
+            stream_id += 1;
+        }
+        env.storage().instance().set(&STREAM_COUNT, &stream_id);
+
+        // One summary per batch so indexers can pick up the whole id range at once
+        if !stream_ids.is_empty() {
+            env.events().publish(
+                (symbol_short!("stream"), symbol_short!("batch")),
+                (first_stream_id, stream_id - 1, stream_ids.len()),
+            );
+        }
+
+        Ok(stream_ids)
+    }
+
+    /// When enabled, batch creation emits only the `batch` summary event and skips
+    /// the per-stream `created` events (Admin only)
+    pub fn set_batch_summary_only(env: Env, admin: Address, enabled: bool) -> Result<(), Error> {
+        admin.require_auth();
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::BatchSummaryOnly, &enabled);
+        Ok(())
+    }
+
+    /// Create a subscription: `periods` payments of `per_period`, each released at the
+    /// end of its `period_seconds` period starting from `start_time`. Deposits
+    /// `per_period * periods` up front and charges fees as `create_stream` does.
+    pub fn create_subscription(
+        env: Env,
+        sender: Address,
+        receiver: Address,
+        token: Address,
+        per_period: i128,
+        period_seconds: u64,
+        periods: u32,
+        start_time: u64,
+    ) -> Result<u64, Error> {
+        let total_amount = per_period
+            .checked_mul(periods as i128)
+            .ok_or(Error::InvalidAmount)?;
+        let end_time = period_seconds
+            .checked_mul(periods as u64)
+            .and_then(|duration| start_time.checked_add(duration))
+            .ok_or(Error::InvalidTimeRange)?;
+
+        Self::create_stream_internal(
+            env.clone(),
+            sender,
+            receiver,
+            token,
+            total_amount,
+            start_time,
+            end_time,
+            Vec::new(&env),
+            CurveType::Step,
+            CurveParams {
+                checkpoints: Vec::new(&env),
+                step_interval: period_seconds,
+            },
+            None,
+            StreamOptions {
+                is_soulbound: false,
+                idempotency_key: None,
+                mutual_cancel: false,
+                revert_after: None,
+                on_cancel_hook: false,
+            },
+        )
+    }
+
+    /// When the next tranche of a step-scheduled stream unlocks, capped at `end_time`.
+    /// Other curves unlock continuously, so for them this is now, clamped to the stream window.
+    pub fn get_next_release_time(env: Env, stream_id: u64) -> Result<u64, Error> {
+        let stream: Stream = env
+            .storage()
+            .instance()
+            .get(&(STREAM_COUNT, stream_id))
+            .ok_or(Error::StreamNotFound)?;
+
+        let now = env.ledger().timestamp();
+        let start = stream.start_time + stream.total_paused_duration;
+        let end = stream.end_time + stream.total_paused_duration;
+        let interval = stream.curve_params.step_interval;
+        let next = match stream.curve_type {
+            CurveType::Step if now >= start => start + ((now - start) / interval + 1) * interval,
+            CurveType::Step => start + interval,
+            _ => now.max(stream.start_time),
+        };
+        Ok(next.min(end))
+    }
+
+    /// Create a stream backed by a token allowance instead of a deposit.
+    ///
+    /// Nothing is transferred up front. The sender must `approve` this contract for at
+    /// least `total_amount`; each withdrawal pulls the withdrawable amount from the
+    /// sender with `transfer_from`. No protocol fee is taken since nothing is deposited.
+    pub fn create_allowance_stream(
+        env: Env,
+        sender: Address,
+        receiver: Address,
+        token: Address,
+        total_amount: i128,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<u64, Error> {
+        sender.require_auth();
+        Self::check_not_paused(&env)?;
+
+        let (curve_type, curve_params) = Self::default_curve(&env);
+        let mut stream = Self::build_stream(
+            &env,
+            &sender,
+            &receiver,
+            &token,
+            total_amount,
+            start_time,
+            end_time,
+            Vec::new(&env),
+            curve_type,
+            curve_params,
+        )?;
+        stream.is_allowance = true;
+
+        if Self::is_address_restricted(env.clone(), receiver.clone()) {
+            soroban_sdk::panic_with_error!(&env, Error::AddressRestricted);
+        }
+        Self::record_stream_creation(&env, &sender);
+
+        let stream_id: u64 = env.storage().instance().get(&STREAM_COUNT).unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&(STREAM_COUNT, stream_id), &stream);
+        env.storage()
+            .instance()
+            .set(&STREAM_COUNT, &(stream_id + 1));
+
+        Self::index_stream(&env, stream_id, &stream);
+        let decimals = Self::cache_token_decimals(&env, &stream.token);
+        Self::emit_stream_created(&env, stream_id, &stream, decimals);
+        Self::mint_receipt(&env, stream_id, &receiver);
+
+        Ok(stream_id)
+    }
+
+    /// Send `amount` of accrued funds to the receiver: from the contract's balance, or
+    /// for allowance streams straight from the sender.
+    fn pay_receiver(env: &Env, stream: &Stream, amount: i128) {
+        let token_client = token::Client::new(env, &stream.token);
+        let contract = env.current_contract_address();
+        if stream.is_allowance {
+            if token_client.allowance(&stream.sender, &contract) < amount {
+                panic!("Insufficient allowance to pay stream");
+            }
+            token_client.transfer_from(&contract, &stream.sender, &stream.receiver, &amount);
+        } else {
+            token_client.transfer(&contract, &stream.receiver, &amount);
+        }
+    }
+
+    /// Set the curve used by creation paths that take no curve argument: batches,
+    /// allowance streams, proposals and contributor requests. Explicit-curve
+    /// constructors are unaffected (Admin only).
+    pub fn set_default_curve(
+        env: Env,
+        admin: Address,
+        curve_type: CurveType,
+        curve_params: CurveParams,
+    ) -> Result<(), Error> {
+        admin.require_auth();
         if !Self::has_role(&env, &admin, Role::Admin) {
-            return; // Error::Unauthorized;
+            return Err(Error::Unauthorized);
+        }
+        match curve_type {
+            CurveType::Custom => Self::validate_checkpoints(&curve_params.checkpoints)?,
+            CurveType::Step if curve_params.step_interval == 0 => return Err(Error::InvalidCurve),
+            _ => {}
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::DefaultCurve, &(curve_type, curve_params));
+        Ok(())
+    }
+
+    /// Curve applied by creation paths without a curve argument (Linear unless set)
+    pub fn get_default_curve(env: Env) -> (CurveType, CurveParams) {
+        Self::default_curve(&env)
+    }
+
+    fn default_curve(env: &Env) -> (CurveType, CurveParams) {
+        env.storage()
+            .instance()
+            .get(&DataKey::DefaultCurve)
+            .unwrap_or((CurveType::Linear, Self::no_curve_params(env)))
+    }
+
+    /// Rebuild a stream stored in the v1 layout, filling the fields v1 lacked with
+    /// the defaults a new stream gets
+    fn stream_from_v1(env: &Env, old: StreamV1) -> Stream {
+        Stream {
+            sender: old.sender,
+            receiver: old.receiver,
+            token: old.token,
+            total_amount: old.total_amount,
+            start_time: old.start_time,
+            end_time: old.end_time,
+            withdrawn: old.withdrawn,
+            withdrawn_amount: old.withdrawn_amount,
+            cancelled: old.cancelled,
+            receipt_owner: old.receipt_owner,
+            is_paused: old.is_paused,
+            paused_time: old.paused_time,
+            total_paused_duration: old.total_paused_duration,
+            milestones: old.milestones,
+            curve_type: old.curve_type,
+            interest_strategy: old.interest_strategy,
+            vault_address: old.vault_address,
+            deposited_principal: old.deposited_principal,
+            metadata: old.metadata,
+            is_usd_pegged: old.is_usd_pegged,
+            usd_amount: old.usd_amount,
+            oracle_address: old.oracle_address,
+            oracle_max_staleness: old.oracle_max_staleness,
+            price_min: old.price_min,
+            price_max: old.price_max,
+            is_soulbound: old.is_soulbound,
+            clawback_enabled: old.clawback_enabled,
+            arbiter: old.arbiter,
+            is_frozen: old.is_frozen,
+            mutual_cancel: false,
+            is_allowance: false,
+            revert_after: None,
+            on_cancel_hook: false,
+            // Resume treats an unknown pauser as the sender
+            paused_by: None,
+            curve_params: Self::no_curve_params(env),
+        }
+    }
+
+    /// Params for curves that take none
+    fn no_curve_params(env: &Env) -> CurveParams {
+        CurveParams {
+            checkpoints: Vec::new(env),
+            step_interval: 0,
+        }
+    }
+
+    /// Custom curves run from (0, 0) to (10000, 10000), never decrease, and advance
+    /// `elapsed_bps` at every step after the first.
+    fn validate_checkpoints(checkpoints: &Vec<(u32, u32)>) -> Result<(), Error> {
+        let len = checkpoints.len();
+        if !(2..=MAX_CURVE_CHECKPOINTS).contains(&len)
+            || checkpoints.first() != Some((0, 0))
+            || checkpoints.last() != Some((10_000, 10_000))
+        {
+            return Err(Error::InvalidCurve);
+        }
+        let mut prev = (0u32, 0u32);
+        for (i, (x, y)) in checkpoints.iter().enumerate() {
+            if i > 0 && (x <= prev.0 || y < prev.1) {
+                return Err(Error::InvalidCurve);
+            }
+            prev = (x, y);
+        }
+        Ok(())
+    }
+
+    /// Validate creation parameters and assemble a fresh `Stream`. Every creation path
+    /// goes through here so invalid input is rejected with the same typed error.
+    fn build_stream(
+        env: &Env,
+        sender: &Address,
+        receiver: &Address,
+        token: &Address,
+        total_amount: i128,
+        start_time: u64,
+        end_time: u64,
+        milestones: Vec<Milestone>,
+        curve_type: CurveType,
+        curve_params: CurveParams,
+    ) -> Result<Stream, Error> {
+        if start_time >= end_time {
+            return Err(Error::InvalidTimeRange);
+        }
+        let min_duration: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinDuration)
+            .unwrap_or(0);
+        if end_time - start_time < min_duration {
+            return Err(Error::InvalidTimeRange);
+        }
+        if total_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        // Milestones must be ordered, fall inside the stream window and cap at 100%
+        let mut last_timestamp = start_time;
+        for milestone in milestones.iter() {
+            if milestone.timestamp < last_timestamp
+                || milestone.timestamp > end_time
+                || milestone.percentage > 100
+            {
+                return Err(Error::InvalidMilestone);
+            }
+            last_timestamp = milestone.timestamp;
+        }
+
+        let interval = curve_params.step_interval;
+        match curve_type {
+            CurveType::Custom => Self::validate_checkpoints(&curve_params.checkpoints)?,
+            CurveType::Step if interval == 0 || interval > end_time - start_time => {
+                return Err(Error::InvalidCurve)
+            }
+            _ => {}
+        }
+
+        Ok(Stream {
+            sender: sender.clone(),
+            receiver: receiver.clone(),
+            token: token.clone(),
+            total_amount,
+            start_time,
+            end_time,
+            withdrawn_amount: 0,
+            interest_strategy: 0,
+            vault_address: None,
+            deposited_principal: total_amount,
+            metadata: None,
+            withdrawn: 0,
+            cancelled: false,
+            receipt_owner: receiver.clone(),
+            is_paused: false,
+            paused_time: 0,
+            total_paused_duration: 0,
+            milestones,
+            curve_type,
+            is_usd_pegged: false,
+            usd_amount: 0,
+            oracle_address: sender.clone(),
+            oracle_max_staleness: 0,
+            price_min: 0,
+            price_max: 0,
+            is_soulbound: false,
+            clawback_enabled: false, // TODO: Check token flags
+            arbiter: None,
+            is_frozen: false,
+            mutual_cancel: false,
+            is_allowance: false,
+            revert_after: None,
+            on_cancel_hook: false,
+            paused_by: None,
+            curve_params,
+        })
+    }
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        // Set admin role
+        env.storage().instance().set(&DataKey::Admin, &admin);
+
+        // Grant all roles to admin
+        env.storage()
+            .instance()
+            .set(&DataKey::Role(admin.clone(), Role::Admin), &true);
+        env.storage()
+            .instance()
+            .set(&DataKey::Role(admin.clone(), Role::Pauser), &true);
+        env.storage()
+            .instance()
+            .set(&DataKey::Role(admin.clone(), Role::TreasuryManager), &true);
+
+        for role in [Role::Admin, Role::Pauser, Role::TreasuryManager] {
+            Self::add_role_member(&env, &admin, role);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+        Ok(())
+    }
+
+    // ========== RBAC Functions ==========
+
+    /// Grant a role to an address (Admin, or holders of the role's admin role)
+    pub fn grant_role(env: Env, admin: Address, target: Address, role: Role) -> Result<(), Error> {
+        admin.require_auth();
+
+        // Check if caller may administer this role
+        if !Self::can_manage_role(&env, &admin, role.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        // Already held: nothing changes, so don't emit a misleading event
+        if Self::has_role(&env, &target, role.clone()) {
+            return Ok(());
+        }
+
+        // Grant the role
+        env.storage()
+            .instance()
+            .set(&DataKey::Role(target.clone(), role.clone()), &true);
+        Self::add_role_member(&env, &target, role.clone());
+
+        // Emit event
+        env.events().publish((symbol_short!("grant"), target), role);
+        Ok(())
+    }
+
+    /// Revoke a role from an address (Admin, or holders of the role's admin role)
+    pub fn revoke_role(env: Env, admin: Address, target: Address, role: Role) -> Result<(), Error> {
+        admin.require_auth();
+
+        // Check if caller may administer this role
+        if !Self::can_manage_role(&env, &admin, role.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        // Not held: nothing to revoke
+        if !Self::has_role(&env, &target, role.clone()) {
+            return Ok(());
         }
 
         // Revoke the role
         env.storage()
             .instance()
             .remove(&DataKey::Role(target.clone(), role.clone()));
+        Self::remove_role_member(&env, &target, role.clone());
 
         // Emit event
         env.events()
             .publish((symbol_short!("revoke"), target), role);
+        Ok(())
     }
 
     /// Check if an address has a specific role
@@ -477,33 +1201,816 @@ impl StellarStreamContract {
         Self::has_role(&env, &address, role)
     }
 
-    /// Internal helper to check if an address has a role
-    fn has_role(env: &Env, address: &Address, role: Role) -> bool {
+    /// Delegate management of `role` to holders of `admin_role` (Admin only). `Admin`
+    /// itself can't be delegated, or a lesser role could mint new admins.
+    pub fn set_role_admin(
+        env: Env,
+        admin: Address,
+        role: Role,
+        admin_role: Role,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        if !Self::has_role(&env, &admin, Role::Admin) || role == Role::Admin {
+            return Err(Error::Unauthorized);
+        }
         env.storage()
             .instance()
-            .get(&DataKey::Role(address.clone(), role))
-            .unwrap_or(false)
+            .set(&DataKey::RoleAdmin(role.clone()), &admin_role);
+        env.events()
+            .publish((symbol_short!("role_adm"), role), admin_role);
+        Ok(())
     }
 
-    // ========== Contract Upgrade Functions ==========
+    /// Role whose holders may grant and revoke `role`; `Admin` unless delegated
+    pub fn get_role_admin(env: Env, role: Role) -> Role {
+        env.storage()
+            .instance()
+            .get(&DataKey::RoleAdmin(role))
+            .unwrap_or(Role::Admin)
+    }
 
-    /// Upgrade the contract to a new WASM hash
-    /// Only addresses with Admin role can perform this operation
-    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: soroban_sdk::BytesN<32>) {
-        admin.require_auth();
+    /// Admin can always manage roles; otherwise the caller needs the role's admin role
+    fn can_manage_role(env: &Env, address: &Address, role: Role) -> bool {
+        Self::has_role(env, address, Role::Admin)
+            || Self::has_role(env, address, Self::get_role_admin(env.clone(), role))
+    }
+
+    /// List every address currently holding `role`
+    pub fn get_role_members(env: Env, role: Role) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::RoleMembers(role))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Every admin, pauser and treasury manager, built from the role-member indexes.
+    /// Supersedes `get_admin`, which only knows the legacy single admin.
+    pub fn get_governance(env: Env) -> Governance {
+        Governance {
+            admins: Self::get_role_members(env.clone(), Role::Admin),
+            pausers: Self::get_role_members(env.clone(), Role::Pauser),
+            treasury_managers: Self::get_role_members(env, Role::TreasuryManager),
+        }
+    }
+
+    fn add_role_member(env: &Env, address: &Address, role: Role) {
+        let key = DataKey::RoleMembers(role);
+        let mut members: Vec<Address> = env.storage().instance().get(&key).unwrap_or(Vec::new(env));
+        if !members.contains(address) {
+            members.push_back(address.clone());
+            env.storage().instance().set(&key, &members);
+        }
+    }
+
+    fn remove_role_member(env: &Env, address: &Address, role: Role) {
+        let key = DataKey::RoleMembers(role);
+        let mut members: Vec<Address> = env.storage().instance().get(&key).unwrap_or(Vec::new(env));
+        if let Some(pos) = members.first_index_of(address) {
+            members.remove(pos);
+            env.storage().instance().set(&key, &members);
+        }
+    }
+
+    /// Internal helper to check if an address has a role
+    fn has_role(env: &Env, address: &Address, role: Role) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Role(address.clone(), role))
+            .unwrap_or(false)
+    }
+
+    // ========== Pause Management ==========
+
+    /// Pause or unpause contract operations (Pauser only). Returns whether the contract
+    /// is paused afterwards.
+    ///
+    /// Pausing always takes a single pauser. When an unpause quorum is set, unpausing
+    /// counts as one approval and only takes effect once the quorum is reached, so
+    /// `set_pause(false)` returns true while the approval is recorded but still short.
+    pub fn set_pause(env: Env, pauser: Address, paused: bool) -> Result<bool, Error> {
+        if !paused {
+            return Self::approve_unpause(env, pauser).map(|unpaused| !unpaused);
+        }
+        pauser.require_auth();
+        if !Self::has_role(&env, &pauser, Role::Pauser) {
+            return Err(Error::Unauthorized);
+        }
+        env.storage().instance().set(&DataKey::IsPaused, &true);
+        env.storage()
+            .instance()
+            .set(&DataKey::PausedAt, &env.ledger().timestamp());
+        // Approvals from an earlier incident don't carry over
+        env.storage().instance().remove(&DataKey::UnpauseApprovals);
+        env.events().publish((symbol_short!("pause"), pauser), true);
+        Ok(true)
+    }
+
+    /// Approve lifting the pause (Pauser only). Returns true once the contract is
+    /// unpaused, i.e. when this approval brings the count up to the unpause quorum.
+    /// Only approvals from current pausers count.
+    pub fn approve_unpause(env: Env, pauser: Address) -> Result<bool, Error> {
+        pauser.require_auth();
+        if !Self::has_role(&env, &pauser, Role::Pauser) {
+            return Err(Error::Unauthorized);
+        }
+
+        let recorded: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::UnpauseApprovals)
+            .unwrap_or(Vec::new(&env));
+        // Drop approvals from pausers whose role was revoked since they approved
+        let mut approvals = Vec::new(&env);
+        for approver in recorded.iter() {
+            if approver != pauser && Self::has_role(&env, &approver, Role::Pauser) {
+                approvals.push_back(approver);
+            }
+        }
+        approvals.push_back(pauser.clone());
+
+        let quorum: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::UnpauseQuorum)
+            .unwrap_or(1);
+        if approvals.len() < quorum {
+            env.storage()
+                .instance()
+                .set(&DataKey::UnpauseApprovals, &approvals);
+            return Ok(false);
+        }
+
+        env.storage().instance().set(&DataKey::IsPaused, &false);
+        env.storage().instance().remove(&DataKey::PausedAt);
+        env.storage().instance().remove(&DataKey::UnpauseApprovals);
+        env.events()
+            .publish((symbol_short!("pause"), pauser), false);
+        Ok(true)
+    }
+
+    /// Number of distinct pauser approvals needed to unpause; 1 by default (Admin only)
+    pub fn set_unpause_quorum(env: Env, admin: Address, quorum: u32) -> Result<(), Error> {
+        admin.require_auth();
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+        if quorum == 0 {
+            return Err(Error::InvalidApprovalThreshold);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::UnpauseQuorum, &quorum);
+        Ok(())
+    }
+
+    /// Effective pause state. A pause older than the configured maximum duration
+    /// lapses on its own, so a lost pauser key cannot freeze funds forever.
+    pub fn is_paused(env: Env) -> bool {
+        let paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::IsPaused)
+            .unwrap_or(false);
+        if !paused {
+            return false;
+        }
+
+        let max_duration: Option<u64> = env.storage().instance().get(&DataKey::MaxPauseDuration);
+        let paused_at: Option<u64> = env.storage().instance().get(&DataKey::PausedAt);
+        match (max_duration, paused_at) {
+            (Some(max_duration), Some(paused_at)) => {
+                env.ledger().timestamp().saturating_sub(paused_at) <= max_duration
+            }
+            _ => true,
+        }
+    }
+
+    /// Set how long a pause may last before it lapses automatically (Admin only)
+    pub fn set_max_pause_duration(env: Env, admin: Address, duration: u64) -> Result<(), Error> {
+        admin.require_auth();
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxPauseDuration, &duration);
+        Ok(())
+    }
+
+    /// Let `cancel` proceed while the contract is paused (Admin only, default false)
+    pub fn set_allow_cancel_while_paused(
+        env: Env,
+        admin: Address,
+        allowed: bool,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AllowCancelWhilePaused, &allowed);
+        Ok(())
+    }
+
+    /// Limit cancellation to `grace` seconds after a stream's end. `None` (the default)
+    /// leaves cancellation open indefinitely (Admin only).
+    pub fn set_cancel_after_end_grace(
+        env: Env,
+        admin: Address,
+        grace: Option<u64>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+        match grace {
+            Some(grace) => env
+                .storage()
+                .instance()
+                .set(&DataKey::CancelAfterEndGrace, &grace),
+            None => env
+                .storage()
+                .instance()
+                .remove(&DataKey::CancelAfterEndGrace),
+        }
+        Ok(())
+    }
+
+    /// Reject new streams shorter than `min_duration` seconds. Any duration of at
+    /// least one second vests correctly; this is a policy guard against streams too
+    /// short to be useful. `None` (the default) removes the floor (Admin only).
+    pub fn set_min_duration(
+        env: Env,
+        admin: Address,
+        min_duration: Option<u64>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+        match min_duration {
+            Some(min_duration) => env
+                .storage()
+                .instance()
+                .set(&DataKey::MinDuration, &min_duration),
+            None => env.storage().instance().remove(&DataKey::MinDuration),
+        }
+        Ok(())
+    }
+
+    /// Override the per-call batch creation cap. `None` restores the
+    /// `MAX_BATCH_SIZE` default (Admin only).
+    pub fn set_max_batch_size(
+        env: Env,
+        admin: Address,
+        max_batch_size: Option<u32>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+        match max_batch_size {
+            Some(max_batch_size) => env
+                .storage()
+                .instance()
+                .set(&DataKey::MaxBatchSize, &max_batch_size),
+            None => env.storage().instance().remove(&DataKey::MaxBatchSize),
+        }
+        Ok(())
+    }
+
+    /// Most requests accepted by one batch creation call
+    pub fn get_max_batch_size(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxBatchSize)
+            .unwrap_or(MAX_BATCH_SIZE)
+    }
+
+    fn check_not_paused(env: &Env) -> Result<(), Error> {
+        if Self::is_paused(env.clone()) {
+            return Err(Error::ContractPaused);
+        }
+        Ok(())
+    }
+
+    // ========== Fee Management ==========
+
+    /// Set the protocol fee and treasury for the first time (TreasuryManager only)
+    pub fn initialize_fee(
+        env: Env,
+        manager: Address,
+        fee_bps: u32,
+        treasury: Address,
+    ) -> Result<(), Error> {
+        Self::require_treasury_manager(&env, &manager)?;
+        if env.storage().instance().has(&DataKey::Treasury) {
+            return Err(Error::AlreadyInitialized);
+        }
+        if fee_bps > MAX_FEE_BPS {
+            return Err(Error::FeeTooHigh);
+        }
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        Ok(())
+    }
+
+    /// Update the protocol fee charged on stream creation (TreasuryManager only)
+    pub fn update_fee(env: Env, manager: Address, fee_bps: u32) -> Result<(), Error> {
+        Self::require_treasury_manager(&env, &manager)?;
+        if fee_bps > MAX_FEE_BPS {
+            return Err(Error::FeeTooHigh);
+        }
+        // A scheduled change that already took effect would keep overriding the update
+        Self::settle_pending_fee(&env);
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        Ok(())
+    }
+
+    /// Update the single treasury that receives fees when no splits are set (TreasuryManager only)
+    pub fn update_treasury(env: Env, manager: Address, treasury: Address) -> Result<(), Error> {
+        Self::require_treasury_manager(&env, &manager)?;
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        Ok(())
+    }
+
+    /// Split protocol fees across several recipients (TreasuryManager only).
+    /// Each entry is (recipient, bps) and the bps must sum to exactly 10000.
+    pub fn set_treasury_splits(
+        env: Env,
+        manager: Address,
+        splits: Vec<(Address, u32)>,
+    ) -> Result<(), Error> {
+        Self::require_treasury_manager(&env, &manager)?;
+        if splits.is_empty() {
+            return Err(Error::InvalidTreasurySplits);
+        }
+        let mut total_bps: u32 = 0;
+        for (_, bps) in splits.iter() {
+            total_bps = total_bps
+                .checked_add(bps)
+                .ok_or(Error::InvalidTreasurySplits)?;
+        }
+        if total_bps != 10_000 {
+            return Err(Error::InvalidTreasurySplits);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::TreasurySplits, &splits);
+        Ok(())
+    }
+
+    /// Charge creation fees in `token` instead of skimming the streamed asset.
+    /// Pass `None` to go back to skimming (TreasuryManager only).
+    pub fn set_fee_token(env: Env, manager: Address, token: Option<Address>) -> Result<(), Error> {
+        Self::require_treasury_manager(&env, &manager)?;
+        match token {
+            Some(token) => env.storage().instance().set(&DataKey::FeeToken, &token),
+            None => env.storage().instance().remove(&DataKey::FeeToken),
+        }
+        Ok(())
+    }
+
+    /// Override the protocol fee for streams of `token`. Pass `None` to fall back to
+    /// the global fee (TreasuryManager only).
+    pub fn set_token_fee(
+        env: Env,
+        manager: Address,
+        token: Address,
+        fee_bps: Option<u32>,
+    ) -> Result<(), Error> {
+        Self::require_treasury_manager(&env, &manager)?;
+        let key = DataKey::TokenFeeBps(token);
+        match fee_bps {
+            Some(fee_bps) if fee_bps > MAX_FEE_BPS => return Err(Error::FeeTooHigh),
+            Some(fee_bps) => env.storage().instance().set(&key, &fee_bps),
+            None => env.storage().instance().remove(&key),
+        }
+        Ok(())
+    }
+
+    /// Schedule the global fee to change to `fee_bps` at `effective_at`, giving users
+    /// notice before it applies. Replaces any earlier pending change (TreasuryManager only).
+    pub fn schedule_fee_change(
+        env: Env,
+        manager: Address,
+        fee_bps: u32,
+        effective_at: u64,
+    ) -> Result<(), Error> {
+        Self::require_treasury_manager(&env, &manager)?;
+        if fee_bps > MAX_FEE_BPS {
+            return Err(Error::FeeTooHigh);
+        }
+        if effective_at <= env.ledger().timestamp() {
+            return Err(Error::InvalidTimeRange);
+        }
+        // Keep a change that already took effect rather than reverting to the old fee
+        Self::settle_pending_fee(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::PendingFee, &(fee_bps, effective_at));
+        Ok(())
+    }
+
+    /// Global fee in effect now, including a scheduled change that has taken effect
+    pub fn get_fee_bps(env: Env) -> u32 {
+        Self::global_fee_bps(&env, env.ledger().timestamp())
+    }
+
+    /// Fee `create_stream` would charge for `token` at `at_time`: the token override if
+    /// set, otherwise the global fee with any scheduled change applied.
+    pub fn get_effective_fee_bps(env: Env, token: Address, at_time: u64) -> u32 {
+        Self::resolve_fee_bps(&env, &token, at_time)
+    }
+
+    /// Preview `create_stream` for `amount` of `token`: `(principal, fee, treasury)`.
+    /// Nothing is stored or transferred. `treasury` is the contract's own address when
+    /// no single treasury would receive the fee (none set, or treasury splits active).
+    pub fn quote_stream(env: Env, token: Address, amount: i128) -> (i128, i128, Address) {
+        let (principal, fee) = Self::creation_fee(&env, &token, amount);
+        let treasury = if env.storage().instance().has(&DataKey::TreasurySplits) {
+            None
+        } else {
+            env.storage().instance().get(&DataKey::Treasury)
+        };
+        (
+            principal,
+            fee,
+            treasury.unwrap_or_else(|| env.current_contract_address()),
+        )
+    }
+
+    fn resolve_fee_bps(env: &Env, token: &Address, at_time: u64) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TokenFeeBps(token.clone()))
+            .unwrap_or_else(|| Self::global_fee_bps(env, at_time))
+    }
+
+    fn global_fee_bps(env: &Env, at_time: u64) -> u32 {
+        let pending: Option<(u32, u64)> = env.storage().instance().get(&DataKey::PendingFee);
+        match pending {
+            Some((fee_bps, effective_at)) if at_time >= effective_at => fee_bps,
+            _ => env.storage().instance().get(&DataKey::FeeBps).unwrap_or(0),
+        }
+    }
+
+    /// Once a scheduled change has taken effect, store it as the global fee and clear it
+    fn settle_pending_fee(env: &Env) {
+        let pending: Option<(u32, u64)> = env.storage().instance().get(&DataKey::PendingFee);
+        if let Some((fee_bps, effective_at)) = pending {
+            if env.ledger().timestamp() >= effective_at {
+                env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+                env.storage().instance().remove(&DataKey::PendingFee);
+            }
+        }
+    }
+
+    fn require_treasury_manager(env: &Env, manager: &Address) -> Result<(), Error> {
+        manager.require_auth();
+        if !Self::has_role(env, manager, Role::TreasuryManager) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Split a creation deposit into `(principal, fee)`. The fee is skimmed from the
+    /// deposit, unless a separate fee token is set, in which case it is charged on top
+    /// in that token and the full amount streams.
+    fn creation_fee(env: &Env, token: &Address, amount: i128) -> (i128, i128) {
+        let fee_bps = Self::resolve_fee_bps(env, token, env.ledger().timestamp());
+        let (principal, fee_amount) = math::fee_split(amount, fee_bps);
+        if env.storage().instance().has(&DataKey::FeeToken) {
+            (amount, fee_amount)
+        } else {
+            (principal, fee_amount)
+        }
+    }
+
+    /// Pull `deposit` of `token` from the sender and pay out `fee_amount`. A zero fee
+    /// skips fee handling entirely, so fee-free contracts never need a treasury.
+    fn collect_deposit(
+        env: &Env,
+        sender: &Address,
+        token: &Address,
+        deposit: i128,
+        fee_amount: i128,
+    ) {
+        let token_client = token::Client::new(env, token);
+        token_client.transfer(sender, &env.current_contract_address(), &deposit);
+        if fee_amount == 0 {
+            return;
+        }
+        let fee_token: Option<Address> = env.storage().instance().get(&DataKey::FeeToken);
+        match fee_token {
+            Some(fee_token) => {
+                let fee_client = token::Client::new(env, &fee_token);
+                fee_client.transfer(sender, &env.current_contract_address(), &fee_amount);
+                Self::distribute_fee(env, &fee_client, fee_amount);
+            }
+            None => Self::distribute_fee(env, &token_client, fee_amount),
+        }
+    }
+
+    /// Pay out a fee held by the contract: pro rata across the treasury splits when
+    /// configured, otherwise to the single treasury. The last split absorbs rounding dust.
+    fn distribute_fee(env: &Env, token_client: &token::Client, fee_amount: i128) {
+        let contract = env.current_contract_address();
+        let splits: Option<Vec<(Address, u32)>> =
+            env.storage().instance().get(&DataKey::TreasurySplits);
+
+        match splits {
+            Some(splits) => {
+                let last = splits.len() - 1;
+                let mut remaining = fee_amount;
+                for (i, (recipient, bps)) in splits.iter().enumerate() {
+                    let share = if i as u32 == last {
+                        remaining
+                    } else {
+                        math::fee_split(fee_amount, bps).1
+                    };
+                    remaining -= share;
+                    if share > 0 {
+                        token_client.transfer(&contract, &recipient, &share);
+                    }
+                }
+            }
+            None => {
+                let treasury: Address = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::Treasury)
+                    .expect("Treasury not set");
+                token_client.transfer(&contract, &treasury, &fee_amount);
+            }
+        }
+    }
+
+    // ========== Contract Upgrade Functions ==========
+
+    /// Schedule an upgrade to `new_wasm_hash`, executable after `UPGRADE_DELAY_SECS`.
+    /// Proposing again replaces the pending upgrade and restarts the delay (Admin only).
+    /// Returns the earliest execution time.
+    pub fn propose_upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<u64, Error> {
+        admin.require_auth();
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        let executable_at = env.ledger().timestamp() + UPGRADE_DELAY_SECS;
+        let pending = PendingUpgrade {
+            wasm_hash: new_wasm_hash.clone(),
+            executable_at,
+            stream_count: env.storage().instance().get(&STREAM_COUNT).unwrap_or(0),
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::UpgradeTimelock, &pending);
+
+        env.events().publish(
+            (symbol_short!("upg_prop"), admin),
+            (new_wasm_hash, executable_at),
+        );
+        Ok(executable_at)
+    }
+
+    /// Returns the upgrade waiting on its timelock, if any
+    pub fn get_pending_upgrade(env: Env) -> Option<PendingUpgrade> {
+        env.storage().instance().get(&DataKey::UpgradeTimelock)
+    }
+
+    /// Upgrade the contract to the proposed WASM hash once its timelock has elapsed.
+    /// Only addresses with Admin role can perform this operation. `new_wasm_hash`
+    /// must match the pending proposal, so the caller confirms what gets installed.
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        // Check if caller has Admin role
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        match Self::get_pending_upgrade(env.clone()) {
+            Some(pending) if pending.wasm_hash == new_wasm_hash => {
+                Self::apply_upgrade(&env, admin, pending)
+            }
+            _ => Err(Error::UpgradeNotProposed),
+        }
+    }
+
+    /// Install the pending upgrade once its timelock has elapsed (Admin only)
+    pub fn execute_upgrade(env: Env, admin: Address) -> Result<(), Error> {
+        admin.require_auth();
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+        let pending = Self::get_pending_upgrade(env.clone()).ok_or(Error::UpgradeNotProposed)?;
+        Self::apply_upgrade(&env, admin, pending)
+    }
+
+    /// Swap in the proposed WASM after checking the timelock and the pre-upgrade
+    /// invariants. Shared by `upgrade` and `execute_upgrade`.
+    fn apply_upgrade(env: &Env, admin: Address, pending: PendingUpgrade) -> Result<(), Error> {
+        if env.ledger().timestamp() < pending.executable_at {
+            return Err(Error::UpgradeTimelocked);
+        }
+
+        Self::check_upgrade_invariants(env, &pending);
+        env.storage().instance().remove(&DataKey::UpgradeTimelock);
+
+        // Update the contract WASM
+        env.deployer()
+            .update_current_contract_wasm(pending.wasm_hash.clone());
+
+        // Emit upgrade event with new WASM hash
+        env.events()
+            .publish((symbol_short!("upgrade"), admin), pending.wasm_hash);
+        Ok(())
+    }
+
+    /// Storage the new code inherits must be consistent, so a violated invariant
+    /// panics rather than installing code on top of it.
+    fn check_upgrade_invariants(env: &Env, pending: &PendingUpgrade) {
+        // The id counter only grows and always points past the newest stream;
+        // a stream stored at the counter would be overwritten by the next create
+        let stream_count: u64 = env.storage().instance().get(&STREAM_COUNT).unwrap_or(0);
+        if stream_count < pending.stream_count
+            || env.storage().instance().has(&(STREAM_COUNT, stream_count))
+        {
+            panic!("Upgrade invariant violated: stream counter");
+        }
+
+        // Every token that open streams still owe must pass `check_solvency`. Streams
+        // live in instance storage, which is loaded whole anyway, so walking them to
+        // find those tokens is bounded by the instance size limit.
+        let mut owed_tokens: Map<Address, bool> = Map::new(env);
+        for stream_id in 0..stream_count {
+            let stream: Option<Stream> = env.storage().instance().get(&(STREAM_COUNT, stream_id));
+            if let Some(stream) = stream {
+                if Self::stream_liability(&stream) > 0 {
+                    owed_tokens.set(stream.token, true);
+                }
+            }
+        }
+        for token in owed_tokens.keys().iter() {
+            let (balance, liability) = Self::check_solvency(env.clone(), token);
+            if balance < liability {
+                panic!("Upgrade invariant violated: liability exceeds balance");
+            }
+        }
+    }
+
+    /// Bring storage written by an older WASM up to `CONTRACT_VERSION` (Admin only).
+    /// Each step runs only when the stored version is below it, so calling this again
+    /// after an upgrade is a no-op. Returns the resulting version.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        admin.require_auth();
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        let version = Self::get_version(env.clone());
+
+        // v2: role holders are enumerable through RoleMembers. Role keys themselves
+        // can't be iterated, so seed from the known holders: the legacy admin and the
+        // caller.
+        if version < 2 {
+            let mut known = Vec::new(&env);
+            known.push_back(admin.clone());
+            let legacy: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+            if let Some(legacy) = legacy {
+                if legacy != admin {
+                    known.push_back(legacy);
+                }
+            }
+            for address in known.iter() {
+                for role in [
+                    Role::Admin,
+                    Role::Pauser,
+                    Role::TreasuryManager,
+                    Role::ComplianceOfficer,
+                ] {
+                    if Self::has_role(&env, &address, role.clone()) {
+                        Self::add_role_member(&env, &address, role);
+                    }
+                }
+            }
+
+            // v2 streams carry six more fields; rewrite every entry still in the v1 layout
+            let curve_params = Symbol::new(&env, "curve_params");
+            let count: u64 = env.storage().instance().get(&STREAM_COUNT).unwrap_or(0);
+            for stream_id in 0..count {
+                let key = (STREAM_COUNT, stream_id);
+                let raw: Option<Map<Symbol, Val>> = env.storage().instance().get(&key);
+                let Some(raw) = raw else { continue };
+                if raw.contains_key(curve_params.clone()) {
+                    continue;
+                }
+                let old = StreamV1::try_from_val(&env, raw.as_val())
+                    .unwrap_or_else(|_| panic!("Unreadable v1 stream"));
+                env.storage()
+                    .instance()
+                    .set(&key, &Self::stream_from_v1(&env, old));
+            }
+        }
+
+        // v3: check_solvency reads a running liability per token; seed it from the
+        // streams written before it was kept
+        if version < 3 {
+            let mut liabilities: Map<Address, i128> = Map::new(&env);
+            let count: u64 = env.storage().instance().get(&STREAM_COUNT).unwrap_or(0);
+            for stream_id in 0..count {
+                let stream: Option<Stream> =
+                    env.storage().instance().get(&(STREAM_COUNT, stream_id));
+                let Some(stream) = stream else { continue };
+                let owed = liabilities.get(stream.token.clone()).unwrap_or(0);
+                liabilities.set(stream.token.clone(), owed + Self::stream_liability(&stream));
+            }
+            for (token, liability) in liabilities.iter() {
+                let key = DataKey::TokenLiability(token);
+                env.storage().persistent().set(&key, &liability);
+                Self::extend_persistent_ttl(&env, &key);
+            }
+        }
+
+        if version < CONTRACT_VERSION {
+            env.storage()
+                .instance()
+                .set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+            env.events()
+                .publish((symbol_short!("migrate"), admin), CONTRACT_VERSION);
+        }
+
+        Ok(CONTRACT_VERSION.max(version))
+    }
+
+    /// Storage layout version; 1 for deployments from before versioning
+    pub fn get_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ContractVersion)
+            .unwrap_or(1)
+    }
+
+    // ========== Creation Rate Limit ==========
+
+    /// Cap how many streams a single sender may create per creation window. `None`
+    /// (the default) lifts the cap (Admin only).
+    pub fn set_max_streams_per_window(
+        env: Env,
+        admin: Address,
+        max_streams: Option<u32>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+        match max_streams {
+            Some(max_streams) => env
+                .storage()
+                .instance()
+                .set(&DataKey::MaxStreamsPerWindow, &max_streams),
+            None => env
+                .storage()
+                .instance()
+                .remove(&DataKey::MaxStreamsPerWindow),
+        }
+        Ok(())
+    }
+
+    /// Returns the per-sender creation cap, or None when creation is unlimited
+    pub fn get_max_streams_per_window(env: Env) -> Option<u32> {
+        env.storage().instance().get(&DataKey::MaxStreamsPerWindow)
+    }
 
-        // Check if caller has Admin role
-        if !Self::has_role(&env, &admin, Role::Admin) {
-            return; // Error::Unauthorized;
-        }
+    /// Count a new stream against the sender's current window, panicking once the cap is hit.
+    /// Nothing is tracked while no cap is configured.
+    fn record_stream_creation(env: &Env, sender: &Address) {
+        let max_streams: u32 = match env.storage().instance().get(&DataKey::MaxStreamsPerWindow) {
+            Some(max) => max,
+            None => return,
+        };
 
-        // Update the contract WASM
-        env.deployer()
-            .update_current_contract_wasm(new_wasm_hash.clone());
+        let bucket = env.ledger().timestamp() / CREATION_WINDOW_SECS;
+        let key = DataKey::CreationCount(sender.clone());
+        let (last_bucket, count): (u64, u32) =
+            env.storage().persistent().get(&key).unwrap_or((bucket, 0));
+        let count = if last_bucket == bucket { count } else { 0 };
 
-        // Emit upgrade event with new WASM hash
-        env.events()
-            .publish((symbol_short!("upgrade"), admin), new_wasm_hash);
+        if count >= max_streams {
+            panic!("Creation rate limit exceeded");
+        }
+        env.storage().persistent().set(&key, &(bucket, count + 1));
+        Self::extend_persistent_ttl(env, &key);
     }
 
     /// Get the current admin address (for backward compatibility)
@@ -588,14 +2095,181 @@ impl StellarStreamContract {
     }
 
     /// Extend instance storage TTL so long-lived streams remain accessible.
-    #[allow(dead_code)]
     fn extend_contract_ttl(env: &Env) {
-        const EXTEND_LEDGERS: u32 = 6_000_000; // ~1 year at 5s/ledger
         env.storage()
             .instance()
             .extend_ttl(EXTEND_LEDGERS, EXTEND_LEDGERS);
     }
 
+    /// Extend a persistent entry's TTL to match the instance's.
+    fn extend_persistent_ttl(env: &Env, key: &DataKey) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, EXTEND_LEDGERS, EXTEND_LEDGERS);
+    }
+
+    /// Publish the indexer-facing `("stream", "created")` event. The data carries every
+    /// stream parameter as `(stream_id, sender, receiver, token, amount, start, cliff, end)`
+    /// so the stream can be rebuilt from events alone. Streams have no separate cliff and
+    /// accrue from `start_time`, so the cliff slot repeats it.
+    fn emit_stream_created(env: &Env, stream_id: u64, stream: &Stream, decimals: u32) {
+        env.events().publish(
+            (symbol_short!("stream"), symbol_short!("created")),
+            (
+                stream_id,
+                stream.sender.clone(),
+                stream.receiver.clone(),
+                stream.token.clone(),
+                stream.total_amount,
+                stream.start_time,
+                stream.start_time,
+                stream.end_time,
+                decimals,
+            ),
+        );
+    }
+
+    /// The token's `decimals`, from the cache filled at stream creation if present.
+    /// Never writes to storage.
+    pub fn get_token_decimals(env: Env, token: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TokenDecimals(token.clone()))
+            .unwrap_or_else(|| token::Client::new(&env, &token).decimals())
+    }
+
+    /// Like `get_token_decimals`, but stores a fresh lookup so later creations skip
+    /// the cross-contract call
+    fn cache_token_decimals(env: &Env, token: &Address) -> u32 {
+        let key = DataKey::TokenDecimals(token.clone());
+        if let Some(decimals) = env.storage().persistent().get(&key) {
+            return decimals;
+        }
+        let decimals = token::Client::new(env, token).decimals();
+        env.storage().persistent().set(&key, &decimals);
+        Self::extend_persistent_ttl(env, &key);
+        decimals
+    }
+
+    /// Add a new stream to the per-token and per-receiver indexes
+    fn index_stream(env: &Env, stream_id: u64, stream: &Stream) {
+        Self::add_to_index(env, StreamIndex::Token(stream.token.clone()), stream_id);
+        Self::add_to_index(
+            env,
+            StreamIndex::Receiver(stream.receiver.clone()),
+            stream_id,
+        );
+    }
+
+    fn index_buckets(env: &Env, index: &StreamIndex) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::IndexBuckets(index.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn set_index_buckets(env: &Env, index: &StreamIndex, buckets: &Vec<u32>) {
+        let key = DataKey::IndexBuckets(index.clone());
+        if buckets.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, buckets);
+            Self::extend_persistent_ttl(env, &key);
+        }
+    }
+
+    /// Insert `stream_id` into its bucket of `index`. The bucket list is only rewritten
+    /// when the bucket was empty.
+    fn add_to_index(env: &Env, index: StreamIndex, stream_id: u64) {
+        let bucket = (stream_id / INDEX_BUCKET_SIZE) as u32;
+        let key = DataKey::IndexBucket(index.clone(), bucket);
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        let Err(pos) = ids.binary_search(stream_id) else {
+            return;
+        };
+        if ids.is_empty() {
+            let mut buckets = Self::index_buckets(env, &index);
+            if let Err(at) = buckets.binary_search(bucket) {
+                buckets.insert(at, bucket);
+                Self::set_index_buckets(env, &index, &buckets);
+            }
+        }
+        ids.insert(pos, stream_id);
+        env.storage().persistent().set(&key, &ids);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    fn remove_from_index(env: &Env, index: StreamIndex, stream_id: u64) {
+        let bucket = (stream_id / INDEX_BUCKET_SIZE) as u32;
+        let key = DataKey::IndexBucket(index.clone(), bucket);
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        let Ok(pos) = ids.binary_search(stream_id) else {
+            return;
+        };
+        ids.remove(pos);
+        if !ids.is_empty() {
+            env.storage().persistent().set(&key, &ids);
+            Self::extend_persistent_ttl(env, &key);
+            return;
+        }
+        env.storage().persistent().remove(&key);
+        let mut buckets = Self::index_buckets(env, &index);
+        if let Ok(at) = buckets.binary_search(bucket) {
+            buckets.remove(at);
+            Self::set_index_buckets(env, &index, &buckets);
+        }
+    }
+
+    /// Up to `limit` ids of `index` at or above `start_id`, ascending. Reads only the
+    /// buckets it returns ids from.
+    fn index_ids(env: &Env, index: &StreamIndex, start_id: u64, limit: u32) -> Vec<u64> {
+        let mut ids = Vec::new(env);
+        let first = (start_id / INDEX_BUCKET_SIZE) as u32;
+        for bucket in Self::index_buckets(env, index).iter() {
+            if ids.len() >= limit {
+                break;
+            }
+            if bucket < first {
+                continue;
+            }
+            let bucket_ids: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::IndexBucket(index.clone(), bucket))
+                .unwrap_or(Vec::new(env));
+            for stream_id in bucket_ids.iter() {
+                if stream_id >= start_id && ids.len() < limit {
+                    ids.push_back(stream_id);
+                }
+            }
+        }
+        ids
+    }
+
+    /// Drop per-stream side data once a stream is cancelled or fully withdrawn so it
+    /// stops paying rent. The stream record and receipt stay for history, vault
+    /// shares stay until they are redeemed, and the idempotency key stays so a late
+    /// retry still resolves to this stream instead of creating a new one.
+    fn purge_stream_storage(env: &Env, stream_id: u64, stream: &Stream) {
+        Self::remove_from_index(env, StreamIndex::Token(stream.token.clone()), stream_id);
+        Self::remove_from_index(
+            env,
+            StreamIndex::Receiver(stream.receiver.clone()),
+            stream_id,
+        );
+        env.storage()
+            .instance()
+            .remove(&DataKey::VotingDelegate(stream_id));
+    }
+
     fn mint_receipt(env: &Env, stream_id: u64, owner: &Address) {
         let receipt = StreamReceipt {
             stream_id,
@@ -614,6 +2288,9 @@ impl StellarStreamContract {
             .ok_or(Error::StreamNotFound)
     }
 
+    /// Seconds of vesting left: `end_time - max(now, start_time)`, saturating at zero.
+    /// Before the start this is the full vesting window; after the end it is 0.
+    /// Pauses push the end back, and while paused the countdown is frozen.
     pub fn get_stream_remaining_time(env: Env, stream_id: u64) -> Result<u64, Error> {
         let stream: Stream = env
             .storage()
@@ -621,13 +2298,102 @@ impl StellarStreamContract {
             .get(&(STREAM_COUNT, stream_id))
             .ok_or(Error::StreamNotFound)?;
 
-        let current_time = env.ledger().timestamp();
-
-        if current_time >= stream.end_time {
-            Ok(0)
+        let current_time = if stream.is_paused {
+            stream.paused_time
         } else {
-            Ok(stream.end_time - current_time)
+            env.ledger().timestamp()
+        };
+        let adjusted_end = stream.end_time + stream.total_paused_duration;
+        Ok(adjusted_end.saturating_sub(current_time.max(stream.start_time)))
+    }
+
+    /// Evenly spaced `(timestamp, cumulative_unlocked)` points from `start_time` to
+    /// `end_time` for charting the vesting curve. `samples` is clamped to 2..=100.
+    /// The curve is sampled as scheduled, ignoring any pauses.
+    pub fn get_unlock_schedule(
+        env: Env,
+        stream_id: u64,
+        samples: u32,
+    ) -> Result<Vec<(u64, i128)>, Error> {
+        let mut stream = Self::get_stream(env.clone(), stream_id)?;
+        stream.is_paused = false;
+        stream.total_paused_duration = 0;
+
+        let samples = samples.clamp(2, MAX_SCHEDULE_SAMPLES);
+        let duration = (stream.end_time - stream.start_time) as u128;
+        let mut points = Vec::new(&env);
+        for i in 0..samples {
+            let offset = (duration * i as u128 / (samples - 1) as u128) as u64;
+            let timestamp = stream.start_time + offset;
+            points.push_back((timestamp, Self::calculate_unlocked(&stream, timestamp)));
+        }
+        Ok(points)
+    }
+
+    /// Non-panicking existence check. Cancelled streams are reported as absent,
+    /// so callers scanning id ranges can skip them.
+    pub fn stream_exists(env: Env, stream_id: u64) -> bool {
+        let stream: Option<Stream> = env.storage().instance().get(&(STREAM_COUNT, stream_id));
+        matches!(stream, Some(s) if !s.cancelled)
+    }
+
+    /// Reads many streams in one call, e.g. for dashboard list views. Each slot holds
+    /// the stream for the id at the same index, or `None` if it is missing or cancelled.
+    pub fn get_streams_batch(env: Env, ids: Vec<u64>) -> Vec<Option<Stream>> {
+        if ids.len() > MAX_BATCH_READ {
+            panic!("Too many stream ids");
+        }
+        let mut streams = Vec::new(&env);
+        for stream_id in ids.iter() {
+            let stream: Option<Stream> = env.storage().instance().get(&(STREAM_COUNT, stream_id));
+            streams.push_back(stream.filter(|s| !s.cancelled));
+        }
+        streams
+    }
+
+    /// Amount vested so far, regardless of how much has already been withdrawn
+    pub fn get_unlocked(env: Env, stream_id: u64) -> Result<i128, Error> {
+        let stream = Self::get_stream(env.clone(), stream_id)?;
+        Ok(Self::calculate_unlocked(&stream, env.ledger().timestamp()))
+    }
+
+    /// Amount still locked in the stream (`total_amount - unlocked`)
+    pub fn get_locked(env: Env, stream_id: u64) -> Result<i128, Error> {
+        let stream = Self::get_stream(env.clone(), stream_id)?;
+        let unlocked = Self::calculate_unlocked(&stream, env.ledger().timestamp());
+        Ok(stream.total_amount - unlocked)
+    }
+
+    /// Total paid out to the receiver so far, e.g. for tax reporting
+    pub fn get_withdrawn_amount(env: Env, stream_id: u64) -> Result<i128, Error> {
+        Ok(Self::get_stream(env, stream_id)?.withdrawn_amount)
+    }
+
+    /// Principal the sender has put into the stream, after the creation fee and
+    /// including any top-ups
+    pub fn get_deposited_amount(env: Env, stream_id: u64) -> Result<i128, Error> {
+        Ok(Self::get_stream(env, stream_id)?.total_amount)
+    }
+
+    /// Tokens per second the stream is paying out right now, truncated. Every curve
+    /// reports its average rate (`total_amount / duration`); the rate is zero before
+    /// the start, after the pause-adjusted end, and while paused or cancelled.
+    pub fn get_stream_rate(env: Env, stream_id: u64) -> Result<i128, Error> {
+        let stream = Self::get_stream(env.clone(), stream_id)?;
+        if stream.cancelled || stream.is_paused {
+            return Ok(0);
         }
+        // Shift `now` back by time spent paused to land on the original schedule
+        let now = env
+            .ledger()
+            .timestamp()
+            .saturating_sub(stream.total_paused_duration);
+        Ok(math::stream_rate(
+            stream.total_amount,
+            stream.start_time,
+            stream.end_time,
+            now,
+        ))
     }
 
     pub fn is_stream_active(env: Env, stream_id: u64) -> bool {
@@ -649,6 +2415,65 @@ impl StellarStreamContract {
             .unwrap_or(Vec::new(&env))
     }
 
+    /// Page through the open streams of `token`, oldest first: up to `limit` ids at or
+    /// above `start_id`. Pass the last id returned plus one to fetch the next page.
+    /// Cancelled and fully withdrawn streams are dropped from the index, so only live
+    /// streams are returned.
+    pub fn get_streams_by_token(env: Env, token: Address, start_id: u64, limit: u32) -> Vec<u64> {
+        Self::index_ids(&env, &StreamIndex::Token(token), start_id, limit)
+    }
+
+    /// Page through the ids of open streams paying `receiver`, oldest first, the same
+    /// way as `get_streams_by_token`
+    pub fn get_streams_by_receiver(
+        env: Env,
+        receiver: Address,
+        start_id: u64,
+        limit: u32,
+    ) -> Vec<u64> {
+        Self::index_ids(&env, &StreamIndex::Receiver(receiver), start_id, limit)
+    }
+
+    /// The contract's own balance of `token`
+    pub fn get_token_balance(env: Env, token: Address) -> i128 {
+        token::Client::new(&env, &token).balance(&env.current_contract_address())
+    }
+
+    /// Reconciliation view for auditors: `(balance, liability)` for `token`, where the
+    /// liability is what open streams still owe out of funds the contract holds. The
+    /// liability is a running total kept up to date by every create, top-up, payout,
+    /// cancel and revert, so this costs the same however many streams are open. A
+    /// balance below the liability means the contract has drifted into insolvency.
+    pub fn check_solvency(env: Env, token: Address) -> (i128, i128) {
+        let liability: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TokenLiability(token.clone()))
+            .unwrap_or(0);
+        (Self::get_token_balance(env, token), liability)
+    }
+
+    /// What `stream` still owes out of the contract's balance. Allowance streams are
+    /// paid by the sender and vault streams by the vault, so they owe nothing here.
+    fn stream_liability(stream: &Stream) -> i128 {
+        if stream.cancelled || stream.is_allowance || stream.vault_address.is_some() {
+            return 0;
+        }
+        stream.total_amount - stream.withdrawn_amount
+    }
+
+    /// Move the running liability of `stream.token` by `delta`, the change in what
+    /// `stream` owes. No-op for streams `stream_liability` doesn't count.
+    fn adjust_liability(env: &Env, stream: &Stream, delta: i128) {
+        if stream.is_allowance || stream.vault_address.is_some() || delta == 0 {
+            return;
+        }
+        let key = DataKey::TokenLiability(stream.token.clone());
+        let liability: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(liability + delta));
+        Self::extend_persistent_ttl(env, &key);
+    }
+
     pub fn transfer_receiver(
         env: Env,
         stream_id: u64,
@@ -679,8 +2504,16 @@ impl StellarStreamContract {
         }
 
         // Update receiver
+        Self::remove_from_index(
+            &env,
+            StreamIndex::Receiver(stream.receiver.clone()),
+            stream_id,
+        );
+        Self::add_to_index(&env, StreamIndex::Receiver(new_receiver.clone()), stream_id);
         stream.receiver = new_receiver.clone();
         env.storage().instance().set(&stream_key, &stream);
+        // Streams live in instance storage, so refresh the instance's lifetime
+        Self::extend_contract_ttl(&env);
 
         Ok(())
     }
@@ -693,6 +2526,7 @@ impl StellarStreamContract {
         amount: i128,
     ) -> Result<(), Error> {
         sender.require_auth();
+        Self::check_not_paused(&env)?;
 
         if amount <= 0 {
             return Err(Error::InvalidAmount);
@@ -718,10 +2552,6 @@ impl StellarStreamContract {
             return Err(Error::InvalidAmount);
         }
 
-        // Transfer tokens from sender
-        let token_client = token::Client::new(&env, &stream.token);
-        token_client.transfer(&sender, &env.current_contract_address(), &amount);
-
         // Calculate new end time based on flow rate
         let total_duration = stream.end_time.saturating_sub(stream.start_time);
         let flow_rate = stream.total_amount / total_duration as i128;
@@ -730,9 +2560,23 @@ impl StellarStreamContract {
         let additional_duration = amount / flow_rate;
         let new_end_time = stream.end_time + additional_duration as u64;
 
+        // The reversion deadline must stay after the end, as at creation
+        if let Some(revert_after) = stream.revert_after {
+            if new_end_time > revert_after {
+                return Err(Error::InvalidTimeRange);
+            }
+        }
+
+        // Transfer tokens from sender; allowance streams just extend the obligation
+        if !stream.is_allowance {
+            let token_client = token::Client::new(&env, &stream.token);
+            token_client.transfer(&sender, &env.current_contract_address(), &amount);
+        }
+
         stream.total_amount = new_total;
         stream.end_time = new_end_time;
         env.storage().instance().set(&key, &stream);
+        Self::adjust_liability(&env, &stream, amount);
 
         env.events().publish(
             (symbol_short!("topup"), stream_id),
@@ -749,6 +2593,8 @@ impl StellarStreamContract {
         Ok(())
     }
 
+    /// Freeze accrual on a stream. Either the sender or the receiver may pause, and
+    /// only that same party (or an Admin) can resume it.
     pub fn pause_stream(env: Env, stream_id: u64, caller: Address) -> Result<(), Error> {
         caller.require_auth();
 
@@ -759,7 +2605,7 @@ impl StellarStreamContract {
             .get(&key)
             .ok_or(Error::StreamNotFound)?;
 
-        if stream.sender != caller {
+        if stream.sender != caller && stream.receiver != caller {
             return Err(Error::Unauthorized);
         }
         if stream.cancelled {
@@ -771,6 +2617,7 @@ impl StellarStreamContract {
 
         stream.is_paused = true;
         stream.paused_time = env.ledger().timestamp();
+        stream.paused_by = Some(caller);
         env.storage().instance().set(&key, &stream);
 
         Ok(())
@@ -786,7 +2633,9 @@ impl StellarStreamContract {
             .get(&key)
             .ok_or(Error::StreamNotFound)?;
 
-        if stream.sender != caller {
+        // Streams paused before `paused_by` existed were paused by their sender
+        let paused_by = stream.paused_by.clone().unwrap_or(stream.sender.clone());
+        if paused_by != caller && !Self::has_role(&env, &caller, Role::Admin) {
             return Err(Error::Unauthorized);
         }
         if stream.cancelled {
@@ -797,10 +2646,11 @@ impl StellarStreamContract {
         }
 
         let current_time = env.ledger().timestamp();
-        let pause_duration = current_time - stream.paused_time;
+        let pause_duration = current_time.saturating_sub(stream.paused_time);
         stream.total_paused_duration += pause_duration;
         stream.is_paused = false;
         stream.paused_time = 0;
+        stream.paused_by = None;
 
         env.storage().instance().set(&key, &stream);
 
@@ -808,7 +2658,35 @@ impl StellarStreamContract {
     }
 
     pub fn withdraw(env: Env, stream_id: u64, caller: Address) -> Result<i128, Error> {
+        Self::withdraw_internal(&env, stream_id, &caller, None)
+    }
+
+    /// Withdraw exactly `requested` tokens, leaving the rest of the unlocked balance
+    /// in the stream. Panics if `requested` is more than is currently withdrawable.
+    pub fn withdraw_amount(
+        env: Env,
+        stream_id: u64,
+        caller: Address,
+        requested: i128,
+    ) -> Result<i128, Error> {
+        if requested <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Self::withdraw_internal(&env, stream_id, &caller, Some(requested))
+    }
+
+    /// Shared withdraw path; `requested == None` withdraws everything available
+    fn withdraw_internal(
+        env: &Env,
+        stream_id: u64,
+        caller: &Address,
+        requested: Option<i128>,
+    ) -> Result<i128, Error> {
         caller.require_auth();
+        Self::check_not_paused(env)?;
+        if Self::is_locked(env) {
+            panic!("Re-entrancy detected");
+        }
 
         let key = (STREAM_COUNT, stream_id);
         let mut stream: Stream = env
@@ -817,7 +2695,7 @@ impl StellarStreamContract {
             .get(&key)
             .ok_or(Error::StreamNotFound)?;
 
-        if stream.receiver != caller {
+        if stream.receiver != *caller {
             return Err(Error::Unauthorized);
         }
 
@@ -830,27 +2708,176 @@ impl StellarStreamContract {
 
         let current_time = env.ledger().timestamp();
         let unlocked = Self::calculate_unlocked(&stream, current_time);
-        let to_withdraw = unlocked - stream.withdrawn_amount;
+        let withdrawable = unlocked - stream.withdrawn_amount;
 
-        if to_withdraw <= 0 {
+        if withdrawable <= 0 {
             return Err(Error::InsufficientBalance);
         }
 
+        let to_withdraw = match requested {
+            Some(requested) if requested > withdrawable => {
+                panic!("Requested exceeds withdrawable")
+            }
+            Some(requested) => requested,
+            None => withdrawable,
+        };
+
         stream.withdrawn_amount += to_withdraw;
         env.storage().instance().set(&key, &stream);
+        Self::adjust_liability(env, &stream, -to_withdraw);
 
-        let token_client = token::Client::new(&env, &stream.token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &stream.receiver,
-            &to_withdraw,
+        // The token contract runs foreign code; hold the lock so it can't re-enter
+        Self::set_lock(env, true);
+        Self::pay_receiver(env, &stream, to_withdraw);
+        Self::set_lock(env, false);
+
+        // `is_final` lets indexers mark the stream complete without polling
+        let is_final = stream.withdrawn_amount == stream.total_amount;
+        if is_final {
+            Self::purge_stream_storage(env, stream_id, &stream);
+        }
+        env.events().publish(
+            (symbol_short!("withdraw"), caller.clone()),
+            (stream_id, to_withdraw, is_final),
         );
 
         Ok(to_withdraw)
     }
 
+    /// Claim everything currently withdrawable from a page of the receiver's streams:
+    /// up to `limit` ids at or above `start_id`, paged the same way as
+    /// `get_streams_by_receiver`. `limit` is capped at `MAX_WITHDRAW_ALL`; paused, not
+    /// yet started or already drained streams in the page are skipped. Pays with one
+    /// transfer per token and returns the total claimed.
+    pub fn withdraw_all(
+        env: Env,
+        receiver: Address,
+        start_id: u64,
+        limit: u32,
+    ) -> Result<i128, Error> {
+        receiver.require_auth();
+        Self::check_not_paused(&env)?;
+        if Self::is_locked(&env) {
+            panic!("Re-entrancy detected");
+        }
+
+        let index = StreamIndex::Receiver(receiver.clone());
+        let current_time = env.ledger().timestamp();
+        // token -> amount owed out of the contract's balance
+        let mut payouts: Map<Address, i128> = Map::new(&env);
+        let mut total: i128 = 0;
+        let ids = Self::index_ids(&env, &index, start_id, limit.min(MAX_WITHDRAW_ALL));
+        for stream_id in ids.iter() {
+            let key = (STREAM_COUNT, stream_id);
+            let mut stream: Stream = match env.storage().instance().get(&key) {
+                Some(stream) => stream,
+                None => continue,
+            };
+            if stream.cancelled || stream.is_paused || stream.receiver != receiver {
+                continue;
+            }
+            let unlocked = Self::calculate_unlocked(&stream, current_time);
+            let to_withdraw = unlocked - stream.withdrawn_amount;
+            if to_withdraw <= 0 {
+                continue;
+            }
+
+            stream.withdrawn_amount += to_withdraw;
+            env.storage().instance().set(&key, &stream);
+            Self::adjust_liability(&env, &stream, -to_withdraw);
+            // Allowance streams pull from the sender, so they can't be grouped
+            if stream.is_allowance {
+                Self::set_lock(&env, true);
+                Self::pay_receiver(&env, &stream, to_withdraw);
+                Self::set_lock(&env, false);
+            } else {
+                let owed = payouts.get(stream.token.clone()).unwrap_or(0);
+                payouts.set(stream.token.clone(), owed + to_withdraw);
+            }
+
+            let is_final = stream.withdrawn_amount == stream.total_amount;
+            if is_final {
+                Self::purge_stream_storage(&env, stream_id, &stream);
+            }
+            env.events().publish(
+                (symbol_short!("withdraw"), receiver.clone()),
+                (stream_id, to_withdraw, is_final),
+            );
+            total += to_withdraw;
+        }
+
+        let contract = env.current_contract_address();
+        Self::set_lock(&env, true);
+        for (token, amount) in payouts.iter() {
+            token::Client::new(&env, &token).transfer(&contract, &receiver, &amount);
+        }
+        Self::set_lock(&env, false);
+
+        Ok(total)
+    }
+
+    /// Push whatever an ended stream still holds to its receiver. Needs no auth, since
+    /// funds can only go to `stream.receiver`, so keeper bots can settle for idle
+    /// receivers. Emits the same final `withdraw` event as a last withdrawal.
+    pub fn finalize_stream(env: Env, stream_id: u64) -> Result<i128, Error> {
+        Self::check_not_paused(&env)?;
+        if Self::is_locked(&env) {
+            panic!("Re-entrancy detected");
+        }
+
+        let key = (STREAM_COUNT, stream_id);
+        let mut stream: Stream = env
+            .storage()
+            .instance()
+            .get(&key)
+            .ok_or(Error::StreamNotFound)?;
+
+        if stream.cancelled {
+            return Err(Error::AlreadyCancelled);
+        }
+        if stream.is_paused {
+            return Err(Error::StreamPaused);
+        }
+        let adjusted_end = stream.end_time + stream.total_paused_duration;
+        if env.ledger().timestamp() < adjusted_end {
+            return Err(Error::StreamNotEnded);
+        }
+
+        let remaining = stream.total_amount - stream.withdrawn_amount;
+        if remaining <= 0 {
+            return Err(Error::InsufficientBalance);
+        }
+
+        stream.withdrawn_amount = stream.total_amount;
+        env.storage().instance().set(&key, &stream);
+        Self::adjust_liability(&env, &stream, -remaining);
+
+        Self::set_lock(&env, true);
+        Self::pay_receiver(&env, &stream, remaining);
+        Self::set_lock(&env, false);
+        Self::purge_stream_storage(&env, stream_id, &stream);
+        env.events().publish(
+            (symbol_short!("withdraw"), stream.receiver.clone()),
+            (stream_id, remaining, true),
+        );
+
+        Ok(remaining)
+    }
+
     pub fn cancel(env: Env, stream_id: u64, caller: Address) -> Result<(), Error> {
         caller.require_auth();
+        // Senders may need to cancel to recover funds during an incident
+        let allow_while_paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllowCancelWhilePaused)
+            .unwrap_or(false);
+        if !allow_while_paused {
+            Self::check_not_paused(&env)?;
+        }
+        if Self::is_locked(&env) {
+            panic!("Re-entrancy detected");
+        }
 
         let key = (STREAM_COUNT, stream_id);
         let mut stream: Stream = env
@@ -865,31 +2892,141 @@ impl StellarStreamContract {
         if stream.cancelled {
             return Err(Error::AlreadyCancelled);
         }
-
         let current_time = env.ledger().timestamp();
+        let grace: Option<u64> = env.storage().instance().get(&DataKey::CancelAfterEndGrace);
+        if let Some(grace) = grace {
+            let adjusted_end = stream.end_time + stream.total_paused_duration;
+            if current_time > adjusted_end.saturating_add(grace) {
+                return Err(Error::StreamEnded);
+            }
+        }
+        // Mutual-consent streams need the counterparty's signature as well. On a
+        // self-stream the caller is both parties and has already signed.
+        if stream.mutual_cancel && stream.sender != stream.receiver {
+            if caller == stream.sender {
+                stream.receiver.require_auth();
+            } else {
+                stream.sender.require_auth();
+            }
+        }
+
         let unlocked = Self::calculate_unlocked(&stream, current_time);
         let to_receiver = unlocked - stream.withdrawn_amount;
         let to_sender = stream.total_amount - unlocked;
 
+        // Everything still owed is paid out here, to one side or the other
+        Self::adjust_liability(&env, &stream, -(to_receiver + to_sender));
         stream.cancelled = true;
         stream.withdrawn_amount = unlocked;
         env.storage().instance().set(&key, &stream);
+        Self::purge_stream_storage(&env, stream_id, &stream);
+
+        // Self-stream: both shares go to the same party, so settle in one transfer
+        if stream.sender == stream.receiver && !stream.is_allowance {
+            let remaining = to_receiver + to_sender;
+            if remaining > 0 {
+                let token_client = token::Client::new(&env, &stream.token);
+                token_client.transfer(&env.current_contract_address(), &stream.sender, &remaining);
+            }
+        } else {
+            if to_receiver > 0 {
+                Self::pay_receiver(&env, &stream, to_receiver);
+            }
+            // Allowance streams hold nothing, so the unvested part simply stays with the sender
+            if to_sender > 0 && !stream.is_allowance {
+                let token_client = token::Client::new(&env, &stream.token);
+                token_client.transfer(&env.current_contract_address(), &stream.sender, &to_sender);
+            }
+        }
+
+        // Notify the receiver only once every transfer has settled, under the lock.
+        // A failing callback is ignored so the receiver can't block its own cancellation.
+        if stream.on_cancel_hook {
+            Self::set_lock(&env, true);
+            let _ = hooks::CancelHookClient::new(&env, &stream.receiver)
+                .try_on_stream_cancelled(&stream_id, &to_receiver);
+            Self::set_lock(&env, false);
+        }
+
+        Ok(())
+    }
+
+    fn is_locked(env: &Env) -> bool {
+        env.storage()
+            .temporary()
+            .get(&DataKey::ReentrancyLock)
+            .unwrap_or(false)
+    }
+
+    /// A panic while the lock is held cannot strand it: a failed invocation rolls
+    /// back every storage write it made, the lock included.
+    fn set_lock(env: &Env, locked: bool) {
+        if locked {
+            env.storage()
+                .temporary()
+                .set(&DataKey::ReentrancyLock, &locked);
+        } else {
+            env.storage().temporary().remove(&DataKey::ReentrancyLock);
+        }
+    }
+
+    /// Reclaim funds the receiver never withdrew once the stream's `revert_after`
+    /// deadline has passed, then delete the stream. Unlike `cancel` this only applies
+    /// to an ended stream, and the whole remaining balance goes back to the sender.
+    /// Pauses push the end back, so a paused stream or one still inside its
+    /// pause-adjusted end can't be reverted even after the deadline.
+    pub fn revert_unclaimed(env: Env, stream_id: u64, sender: Address) -> Result<i128, Error> {
+        sender.require_auth();
+        Self::check_not_paused(&env)?;
+
+        let key = (STREAM_COUNT, stream_id);
+        let stream: Stream = env
+            .storage()
+            .instance()
+            .get(&key)
+            .ok_or(Error::StreamNotFound)?;
+
+        if stream.sender != sender {
+            return Err(Error::Unauthorized);
+        }
+        if stream.cancelled {
+            return Err(Error::AlreadyCancelled);
+        }
+        let now = env.ledger().timestamp();
+        match stream.revert_after {
+            Some(revert_after) if now >= revert_after => {}
+            _ => return Err(Error::RevertNotAvailable),
+        }
+        if stream.is_paused || now < stream.end_time + stream.total_paused_duration {
+            return Err(Error::StreamNotEnded);
+        }
+
+        let remaining = stream.total_amount - stream.withdrawn_amount;
+        if remaining <= 0 {
+            return Err(Error::InsufficientBalance);
+        }
+
+        env.storage().instance().remove(&key);
+        env.storage().instance().remove(&(RECEIPT, stream_id));
+        Self::adjust_liability(&env, &stream, -remaining);
+        Self::purge_stream_storage(&env, stream_id, &stream);
 
-        let token_client = token::Client::new(&env, &stream.token);
-        if to_receiver > 0 {
-            token_client.transfer(
+        // Allowance streams never took custody, so there is nothing to send back
+        if !stream.is_allowance {
+            token::Client::new(&env, &stream.token).transfer(
                 &env.current_contract_address(),
-                &stream.receiver,
-                &to_receiver,
+                &sender,
+                &remaining,
             );
         }
-        if to_sender > 0 {
-            token_client.transfer(&env.current_contract_address(), &stream.sender, &to_sender);
-        }
 
-        Ok(())
+        env.events()
+            .publish((symbol_short!("reverted"), sender), (stream_id, remaining));
+        Ok(remaining)
     }
 
+    /// Streams carry no separate cliff; accrual begins at `start_time`, which is what
+    /// a cliff equal to the start would give.
     fn calculate_unlocked(stream: &Stream, current_time: u64) -> i128 {
         if current_time <= stream.start_time {
             return 0;
@@ -905,7 +3042,8 @@ impl StellarStreamContract {
             return stream.total_amount;
         }
 
-        let elapsed = (effective_time - stream.start_time) as i128;
+        // A stream paused before its start has an effective time before the start too
+        let elapsed = effective_time.saturating_sub(stream.start_time) as i128;
         let paused = stream.total_paused_duration as i128;
         let effective_elapsed = elapsed - paused;
 
@@ -918,6 +3056,19 @@ impl StellarStreamContract {
         // Calculate base unlocked amount based on curve type
         match stream.curve_type {
             CurveType::Linear => (stream.total_amount * effective_elapsed) / duration,
+            CurveType::Custom => math::calculate_custom_unlocked(
+                stream.total_amount,
+                &stream.curve_params.checkpoints,
+                effective_elapsed,
+                duration,
+            )
+            .unwrap_or((stream.total_amount * effective_elapsed) / duration),
+            CurveType::Step => {
+                // Only whole intervals count
+                let interval = stream.curve_params.step_interval as i128;
+                let released = (effective_elapsed / interval) * interval;
+                (stream.total_amount * released) / duration
+            }
             CurveType::Exponential => {
                 // Use exponential curve with overflow protection
                 let adjusted_start = stream.start_time;
@@ -999,7 +3150,8 @@ impl StellarStreamContract {
         env.storage()
             .instance()
             .set(&RequestKey::Request(request_id), &request);
-        let stream_id = Self::create_stream(
+        let (curve_type, curve_params) = Self::default_curve(&env);
+        let stream_id = Self::create_stream_with_curve(
             env.clone(),
             admin.clone(),
             request.receiver.clone(),
@@ -1007,8 +3159,8 @@ impl StellarStreamContract {
             request.total_amount,
             request.start_time,
             request.start_time + request.duration,
-            CurveType::Linear,
-            false, // is_soulbound
+            curve_type,
+            curve_params,
         )?;
         env.events().publish(
             (
@@ -1,5 +1,7 @@
 #![allow(unexpected_cfgs)]
 
+use soroban_sdk::Vec;
+
 /// Calculate unlocked amount with precision-safe rounding
 /// Always rounds DOWN to favor contract solvency
 #[allow(dead_code)]
@@ -17,8 +19,8 @@ pub fn calculate_unlocked_amount(
         return total_amount;
     }
 
-    let elapsed_time = (current_time - start_time) as i128;
-    let total_duration = (end_time - start_time) as i128;
+    let elapsed_time = current_time.saturating_sub(start_time) as i128;
+    let total_duration = end_time.saturating_sub(start_time) as i128;
 
     // Integer division automatically rounds down (floor division)
     // This ensures we never unlock more than we should
@@ -42,8 +44,8 @@ pub fn calculate_exponential_unlocked(
         return Ok(total_amount);
     }
 
-    let elapsed = (current_time - start_time) as i128;
-    let duration = (end_time - start_time) as i128;
+    let elapsed = current_time.saturating_sub(start_time) as i128;
+    let duration = end_time.saturating_sub(start_time) as i128;
 
     // Quadratic formula: unlocked = total * (elapsed^2 / duration^2)
     // Rearranged to minimize overflow: (total * elapsed * elapsed) / (duration * duration)
@@ -54,6 +56,50 @@ pub fn calculate_exponential_unlocked(
     Ok(numerator / duration_squared)
 }
 
+/// Calculate unlocked amount for a custom checkpoint curve
+/// Finds the `(elapsed_bps, unlocked_bps)` segment containing `elapsed / duration` and
+/// interpolates linearly inside it, rounding DOWN. Uses checked math to prevent overflow
+pub fn calculate_custom_unlocked(
+    total_amount: i128,
+    checkpoints: &Vec<(u32, u32)>,
+    elapsed: i128,
+    duration: i128,
+) -> Result<i128, ()> {
+    if elapsed <= 0 {
+        return Ok(0);
+    }
+    if elapsed >= duration {
+        return Ok(total_amount);
+    }
+
+    // Work in bps * duration units so the position is exact
+    let position = elapsed.checked_mul(10_000).ok_or(())?;
+    let mut prev = (0u32, 0u32);
+    for (x, y) in checkpoints.iter() {
+        let x_pos = (x as i128).checked_mul(duration).ok_or(())?;
+        if position <= x_pos && x > prev.0 {
+            let (x0, y0) = (prev.0 as i128, prev.1 as i128);
+            let (x1, y1) = (x as i128, y as i128);
+            let span = x1 - x0;
+            // unlocked_bps = y0 + (y1 - y0) * (position - x0 * duration) / (span * duration)
+            let offset = position - x0 * duration;
+            let numerator = y0
+                .checked_mul(span)
+                .and_then(|v| v.checked_mul(duration))
+                .and_then(|v| v.checked_add((y1 - y0).checked_mul(offset)?))
+                .ok_or(())?;
+            let denominator = span
+                .checked_mul(duration)
+                .ok_or(())?
+                .checked_mul(10_000)
+                .ok_or(())?;
+            return Ok(total_amount.checked_mul(numerator).ok_or(())? / denominator);
+        }
+        prev = (x, y);
+    }
+    Ok(total_amount)
+}
+
 /// Calculate withdrawable amount
 /// For final withdrawal, caller should use total_amount - withdrawn_amount
 /// to avoid precision loss
@@ -78,8 +124,13 @@ pub fn calculate_unlocked(total_amount: i128, start: u64, cliff: u64, end: u64,
         return total_amount;
     }
 
-    let elapsed = (now - start) as i128;
-    let total_duration = (end - start) as i128;
+    // A cliff set before the start can let `now` pass the cliff before the start
+    if now <= start {
+        return 0;
+    }
+
+    let elapsed = now.saturating_sub(start) as i128;
+    let total_duration = end.saturating_sub(start) as i128;
 
     // Integer division rounds down (floor), favoring contract solvency
     // This prevents over-withdrawal due to rounding errors
@@ -120,6 +171,28 @@ pub fn calculate_fee(amount: i128, fee_bps: u32) -> i128 {
     (amount * (fee_bps as i128)) / 10_000
 }
 
+/// Split `amount` into `(principal, fee)` at `fee_bps`. The fee rounds down and the
+/// principal takes the remainder, so `principal + fee == amount` always holds.
+/// Splitting on the 10_000 denominator first keeps large amounts from overflowing.
+pub fn fee_split(amount: i128, fee_bps: u32) -> (i128, i128) {
+    if fee_bps == 0 || amount <= 0 {
+        return (amount, 0);
+    }
+    let bps = fee_bps as i128;
+    let fee = (amount / 10_000) * bps + (amount % 10_000) * bps / 10_000;
+    (amount - fee, fee)
+}
+
+/// Tokens per second flowing at `now`, truncated: `total_amount / (end - start)`
+/// while `start <= now < end` and zero outside that window. Nonlinear curves are
+/// reported at this same average rate rather than their momentary slope.
+pub fn stream_rate(total_amount: i128, start: u64, end: u64, now: u64) -> i128 {
+    if now < start || now >= end {
+        return 0;
+    }
+    total_amount / (end - start) as i128
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -150,6 +223,25 @@ mod test {
         assert_eq!(calculate_unlocked(total, start, cliff, end, 1000), 1000);
     }
 
+    #[test]
+    fn test_now_before_start_never_underflows() {
+        let total = 1000_i128;
+
+        assert_eq!(calculate_unlocked_amount(total, 500, 1000, 0), 0);
+        assert_eq!(
+            calculate_exponential_unlocked(total, 500, 1000, 0).unwrap(),
+            0
+        );
+
+        // Cliff before start: `now` can pass the cliff while still before the start
+        assert_eq!(calculate_unlocked(total, 500, 100, 1000, 200), 0);
+        assert_eq!(calculate_withdrawable(total, 0, 500, 100, 1000, 200), 0);
+        assert_eq!(calculate_unlocked(total, 500, 100, 1000, 750), 500);
+
+        // Degenerate window with everything before `now`
+        assert_eq!(calculate_unlocked(total, 500, 100, 500, 300), 0);
+    }
+
     #[test]
     fn test_exponential_curve() {
         let total = 1000_i128;
@@ -187,6 +279,47 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_fee_split_uneven_amounts() {
+        // 1% of 999 is 9.99: the fee rounds down, the principal keeps the dust
+        assert_eq!(fee_split(999, 100), (990, 9));
+        assert_eq!(fee_split(1, 1000), (1, 0));
+        assert_eq!(fee_split(12_345, 33), (12_305, 40));
+        assert_eq!(fee_split(1000, 0), (1000, 0));
+        assert_eq!(fee_split(0, 100), (0, 0));
+
+        for amount in [1_i128, 7, 99, 10_001, 123_456_789, i128::MAX] {
+            for bps in [1_u32, 50, 333, 1000] {
+                let (principal, fee) = fee_split(amount, bps);
+                assert_eq!(principal + fee, amount);
+                assert!(fee >= 0 && fee <= amount / 10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fee_split_matches_calculate_fee() {
+        for amount in [1_i128, 999, 10_000, 1_000_003] {
+            for bps in [1_u32, 100, 999, 1000] {
+                assert_eq!(fee_split(amount, bps).1, calculate_fee(amount, bps));
+            }
+        }
+    }
+
+    #[test]
+    fn test_stream_rate() {
+        assert_eq!(stream_rate(1000, 100, 200, 100), 10);
+        assert_eq!(stream_rate(1000, 100, 200, 199), 10);
+        // Truncated towards zero
+        assert_eq!(stream_rate(1000, 0, 300, 0), 3);
+        assert_eq!(stream_rate(100, 0, 1000, 500), 0);
+
+        // Nothing flows outside the window
+        assert_eq!(stream_rate(1000, 100, 200, 99), 0);
+        assert_eq!(stream_rate(1000, 100, 200, 200), 0);
+        assert_eq!(stream_rate(1000, 100, 100, 100), 0);
+    }
+
     #[test]
     fn test_exponential_overflow_protection() {
         // Test with large values that could overflow
@@ -0,0 +1,239 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    storage::STREAM_COUNT,
+    test_helpers::{setup, TestContext},
+    types::{CurveType, DataKey, Role, Stream, StreamV1},
+    StellarStreamContractClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    vec, Address, Env,
+};
+
+/// Strip the state a pre-versioning deployment would not have written.
+fn downgrade_storage(env: &Env, client: &StellarStreamContractClient) {
+    env.as_contract(&client.address, || {
+        let storage = env.storage().instance();
+        storage.remove(&DataKey::ContractVersion);
+        for role in [Role::Admin, Role::Pauser, Role::TreasuryManager] {
+            storage.remove(&DataKey::RoleMembers(role));
+        }
+    });
+}
+
+/// Rewrite a stream in the layout a v1 deployment stored.
+fn downgrade_stream(env: &Env, client: &StellarStreamContractClient, stream_id: u64) {
+    env.as_contract(&client.address, || {
+        let storage = env.storage().instance();
+        let stream: Stream = storage.get(&(STREAM_COUNT, stream_id)).unwrap();
+        let old = StreamV1 {
+            sender: stream.sender,
+            receiver: stream.receiver,
+            token: stream.token,
+            total_amount: stream.total_amount,
+            start_time: stream.start_time,
+            end_time: stream.end_time,
+            withdrawn: stream.withdrawn,
+            withdrawn_amount: stream.withdrawn_amount,
+            cancelled: stream.cancelled,
+            receipt_owner: stream.receipt_owner,
+            is_paused: stream.is_paused,
+            paused_time: stream.paused_time,
+            total_paused_duration: stream.total_paused_duration,
+            milestones: stream.milestones,
+            curve_type: stream.curve_type,
+            interest_strategy: stream.interest_strategy,
+            vault_address: stream.vault_address,
+            deposited_principal: stream.deposited_principal,
+            metadata: stream.metadata,
+            is_usd_pegged: stream.is_usd_pegged,
+            usd_amount: stream.usd_amount,
+            oracle_address: stream.oracle_address,
+            oracle_max_staleness: stream.oracle_max_staleness,
+            price_min: stream.price_min,
+            price_max: stream.price_max,
+            is_soulbound: stream.is_soulbound,
+            clawback_enabled: stream.clawback_enabled,
+            arbiter: stream.arbiter,
+            is_frozen: stream.is_frozen,
+        };
+        storage.set(&(STREAM_COUNT, stream_id), &old);
+    });
+}
+
+#[test]
+fn test_fresh_deployment_is_current() {
+    let TestContext {
+        env, client, admin, ..
+    } = setup();
+
+    assert_eq!(client.get_version(), 3);
+    assert_eq!(client.get_role_members(&Role::Admin), vec![&env, admin]);
+}
+
+#[test]
+fn test_upgrade_then_migrate_seeds_role_members() {
+    let TestContext {
+        env, client, admin, ..
+    } = setup();
+    let guardian = Address::generate(&env);
+    client.grant_role(&admin, &guardian, &Role::Pauser);
+
+    let sender = Address::generate(&env);
+    let receiver = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token_id).mint(&sender, &1000);
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+    let before = client.get_stream(&stream_id);
+    let assert_stream_intact = || {
+        let after = client.get_stream(&stream_id);
+        assert_eq!(after.sender, before.sender);
+        assert_eq!(after.receiver, before.receiver);
+        assert_eq!(after.total_amount, before.total_amount);
+        assert_eq!(after.end_time, before.end_time);
+    };
+
+    downgrade_storage(&env, &client);
+    assert_eq!(client.get_version(), 1);
+    assert_eq!(client.get_role_members(&Role::Admin).len(), 0);
+
+    assert_eq!(client.migrate(&admin), 3);
+    assert_eq!(client.get_version(), 3);
+    assert_eq!(
+        client.get_role_members(&Role::Admin),
+        vec![&env, admin.clone()]
+    );
+    assert_eq!(
+        client.get_role_members(&Role::TreasuryManager),
+        vec![&env, admin.clone()]
+    );
+    assert_stream_intact();
+
+    // Role keys are untouched, even for holders the seed can't discover
+    assert!(client.check_role(&guardian, &Role::Pauser));
+
+    // Running again changes nothing
+    assert_eq!(client.migrate(&admin), 3);
+    assert_eq!(client.get_role_members(&Role::Admin).len(), 1);
+    assert_stream_intact();
+}
+
+#[test]
+fn test_role_members_track_grants_and_revokes() {
+    let TestContext {
+        env, client, admin, ..
+    } = setup();
+    let guardian = Address::generate(&env);
+
+    client.grant_role(&admin, &guardian, &Role::Pauser);
+    client.grant_role(&admin, &guardian, &Role::Pauser);
+    assert_eq!(
+        client.get_role_members(&Role::Pauser),
+        vec![&env, admin.clone(), guardian.clone()]
+    );
+
+    client.revoke_role(&admin, &guardian, &Role::Pauser);
+    assert_eq!(client.get_role_members(&Role::Pauser), vec![&env, admin]);
+}
+
+#[test]
+fn test_governance_lists_every_role_holder() {
+    let TestContext {
+        env, client, admin, ..
+    } = setup();
+    let co_admin = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let night_guardian = Address::generate(&env);
+    let treasurer = Address::generate(&env);
+
+    client.grant_role(&admin, &co_admin, &Role::Admin);
+    client.grant_role(&admin, &guardian, &Role::Pauser);
+    client.grant_role(&admin, &night_guardian, &Role::Pauser);
+    client.grant_role(&admin, &treasurer, &Role::TreasuryManager);
+
+    let governance = client.get_governance();
+    assert_eq!(governance.admins, vec![&env, admin.clone(), co_admin]);
+    assert_eq!(
+        governance.pausers,
+        vec![&env, admin.clone(), guardian, night_guardian]
+    );
+    assert_eq!(
+        governance.treasury_managers,
+        vec![&env, admin.clone(), treasurer]
+    );
+
+    // The legacy view still reports the original admin
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_migrate_requires_admin() {
+    let TestContext { env, client, .. } = setup();
+
+    let result = client.try_migrate(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_migrate_rewrites_v1_streams() {
+    let TestContext {
+        env, client, admin, ..
+    } = setup();
+    let sender = Address::generate(&env);
+    let receiver = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token_id).mint(&sender, &2000);
+    let ids = [CurveType::Linear, CurveType::Exponential].map(|curve| {
+        client.create_stream(
+            &sender, &receiver, &token_id, &1000, &0, &1000, &curve, &false,
+        )
+    });
+
+    downgrade_storage(&env, &client);
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::TokenLiability(token_id.clone()));
+    });
+    for stream_id in ids {
+        downgrade_stream(&env, &client, stream_id);
+        assert!(client.try_get_stream(&stream_id).is_err());
+    }
+
+    assert_eq!(client.migrate(&admin), 3);
+    assert_eq!(client.check_solvency(&token_id), (2000, 2000));
+
+    let linear = client.get_stream(&ids[0]);
+    assert_eq!(linear.curve_type, CurveType::Linear);
+    assert_eq!(linear.total_amount, 1000);
+    assert!(!linear.mutual_cancel && !linear.is_allowance && !linear.on_cancel_hook);
+    assert_eq!(linear.revert_after, None);
+    assert_eq!(linear.paused_by, None);
+    assert_eq!(linear.curve_params.checkpoints.len(), 0);
+    assert_eq!(
+        client.get_stream(&ids[1]).curve_type,
+        CurveType::Exponential
+    );
+
+    // Migrated streams behave as before
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.withdraw(&ids[0], &receiver), 500);
+    assert_eq!(client.withdraw(&ids[1], &receiver), 250);
+    assert_eq!(client.check_solvency(&token_id), (1250, 1250));
+}
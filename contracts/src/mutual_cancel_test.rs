@@ -0,0 +1,128 @@
+#![cfg(test)]
+
+use crate::{
+    test_helpers::{setup, TestContext},
+    types::{CurveType, StreamOptions},
+    StellarStreamContractClient,
+};
+use soroban_sdk::{
+    testutils::{Ledger, MockAuth, MockAuthInvoke},
+    Address, Env, IntoVal,
+};
+
+fn create(ctx: &TestContext, mutual_cancel: bool) -> u64 {
+    ctx.client.create_stream_with_options(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &StreamOptions {
+            is_soulbound: false,
+            idempotency_key: None,
+            mutual_cancel,
+            revert_after: None,
+            on_cancel_hook: false,
+        },
+    )
+}
+
+/// Sign the `cancel(stream_id, caller)` invocation with `signer`, and `cosigner` if given.
+fn mock_cancel_auths(
+    env: &Env,
+    client: &StellarStreamContractClient,
+    stream_id: u64,
+    caller: &Address,
+    signer: &Address,
+    cosigner: Option<&Address>,
+) {
+    let invoke = MockAuthInvoke {
+        contract: &client.address,
+        fn_name: "cancel",
+        args: (stream_id, caller.clone()).into_val(env),
+        sub_invokes: &[],
+    };
+    let first = MockAuth {
+        address: signer,
+        invoke: &invoke,
+    };
+    match cosigner {
+        Some(cosigner) => env.mock_auths(&[
+            first,
+            MockAuth {
+                address: cosigner,
+                invoke: &invoke,
+            },
+        ]),
+        None => env.mock_auths(&[first]),
+    };
+}
+
+#[test]
+fn test_mutual_cancel_rejects_single_signature() {
+    let ctx = setup();
+    let stream_id = create(&ctx, true);
+
+    mock_cancel_auths(
+        &ctx.env,
+        &ctx.client,
+        stream_id,
+        &ctx.sender,
+        &ctx.sender,
+        None,
+    );
+    assert!(ctx.client.try_cancel(&stream_id, &ctx.sender).is_err());
+
+    mock_cancel_auths(
+        &ctx.env,
+        &ctx.client,
+        stream_id,
+        &ctx.receiver,
+        &ctx.receiver,
+        None,
+    );
+    assert!(ctx.client.try_cancel(&stream_id, &ctx.receiver).is_err());
+
+    assert!(!ctx.client.get_stream(&stream_id).cancelled);
+}
+
+#[test]
+fn test_mutual_cancel_with_both_signatures() {
+    let ctx = setup();
+    let stream_id = create(&ctx, true);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 400);
+    mock_cancel_auths(
+        &ctx.env,
+        &ctx.client,
+        stream_id,
+        &ctx.sender,
+        &ctx.sender,
+        Some(&ctx.receiver),
+    );
+    ctx.client.cancel(&stream_id, &ctx.sender);
+
+    assert!(ctx.client.get_stream(&stream_id).cancelled);
+    assert_eq!(ctx.token.balance(&ctx.receiver), 400);
+    assert_eq!(ctx.token.balance(&ctx.sender), 9_600);
+}
+
+#[test]
+fn test_single_party_cancel_remains_default() {
+    let ctx = setup();
+    let stream_id = create(&ctx, false);
+
+    mock_cancel_auths(
+        &ctx.env,
+        &ctx.client,
+        stream_id,
+        &ctx.sender,
+        &ctx.sender,
+        None,
+    );
+    ctx.client.cancel(&stream_id, &ctx.sender);
+
+    assert!(ctx.client.get_stream(&stream_id).cancelled);
+}
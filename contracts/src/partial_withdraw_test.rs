@@ -0,0 +1,103 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup, TestContext},
+    types::CurveType,
+};
+use soroban_sdk::testutils::Ledger;
+
+#[test]
+fn test_partial_withdraw_then_remainder() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        token,
+        ..
+    } = setup();
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+
+    // 500 unlocked, take only 200
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.withdraw_amount(&stream_id, &receiver, &200), 200);
+    assert_eq!(token.balance(&receiver), 200);
+    assert_eq!(client.get_stream(&stream_id).withdrawn_amount, 200);
+
+    // Take exactly the remaining 300
+    assert_eq!(client.withdraw_amount(&stream_id, &receiver, &300), 300);
+    assert_eq!(token.balance(&receiver), 500);
+    assert_eq!(client.get_stream(&stream_id).withdrawn_amount, 500);
+
+    // Nothing left until more unlocks
+    let result = client.try_withdraw_amount(&stream_id, &receiver, &1);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+
+    // Full withdraw still takes everything that remains
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 500);
+    assert_eq!(token.balance(&receiver), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Requested exceeds withdrawable")]
+fn test_partial_withdraw_exceeding_withdrawable() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    client.withdraw_amount(&stream_id, &receiver, &501);
+}
+
+#[test]
+fn test_partial_withdraw_rejects_non_positive() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    let result = client.try_withdraw_amount(&stream_id, &receiver, &0);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+}
@@ -0,0 +1,182 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::setup,
+    types::{CurveType, Role},
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_pause_blocks_operations() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+
+    ctx.client.set_pause(&ctx.admin, &true);
+    assert!(ctx.client.is_paused());
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 500);
+    let result = ctx.client.try_withdraw(&stream_id, &ctx.receiver);
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+
+    let result = ctx.client.try_create_stream(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+
+    ctx.client.set_pause(&ctx.admin, &false);
+    assert_eq!(ctx.client.withdraw(&stream_id, &ctx.receiver), 500);
+}
+
+#[test]
+fn test_cancel_blocked_while_paused_by_default() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+
+    ctx.client.set_pause(&ctx.admin, &true);
+
+    let result = ctx.client.try_cancel(&stream_id, &ctx.sender);
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+    assert!(!ctx.client.get_stream(&stream_id).cancelled);
+}
+
+#[test]
+fn test_cancel_allowed_while_paused_when_flag_set() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+
+    ctx.client.set_allow_cancel_while_paused(&ctx.admin, &true);
+    ctx.client.set_pause(&ctx.admin, &true);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 250);
+    ctx.client.cancel(&stream_id, &ctx.sender);
+
+    assert!(ctx.client.get_stream(&stream_id).cancelled);
+    assert_eq!(ctx.token.balance(&ctx.receiver), 250);
+    assert_eq!(ctx.token.balance(&ctx.sender), 9_750);
+
+    // Other operations stay blocked
+    let result = ctx.client.try_withdraw(&stream_id, &ctx.receiver);
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+}
+
+#[test]
+fn test_pause_config_requires_roles() {
+    let ctx = setup();
+
+    let result = ctx.client.try_set_pause(&ctx.sender, &true);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    let result = ctx
+        .client
+        .try_set_allow_cancel_while_paused(&ctx.sender, &true);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_pause_lapses_after_max_duration() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+
+    ctx.client.set_max_pause_duration(&ctx.admin, &100);
+    ctx.env.ledger().with_mut(|li| li.timestamp = 200);
+    ctx.client.set_pause(&ctx.admin, &true);
+
+    // Still inside the pause window
+    ctx.env.ledger().with_mut(|li| li.timestamp = 300);
+    assert!(ctx.client.is_paused());
+    let result = ctx.client.try_withdraw(&stream_id, &ctx.receiver);
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+
+    // Past the window: operations resume with no unpause transaction
+    ctx.env.ledger().with_mut(|li| li.timestamp = 301);
+    assert!(!ctx.client.is_paused());
+    assert_eq!(ctx.client.withdraw(&stream_id, &ctx.receiver), 301);
+}
+
+#[test]
+fn test_pause_without_max_duration_does_not_lapse() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+
+    ctx.client.set_pause(&ctx.admin, &true);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+    let result = ctx.client.try_withdraw(&stream_id, &ctx.receiver);
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+
+    let result = ctx.client.try_set_max_pause_duration(&ctx.sender, &10);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_unpause_requires_quorum_of_pausers() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+    let guardian = Address::generate(&ctx.env);
+    ctx.client.grant_role(&ctx.admin, &guardian, &Role::Pauser);
+    ctx.client.set_unpause_quorum(&ctx.admin, &2);
+
+    // Pausing still takes a single pauser
+    ctx.client.set_pause(&guardian, &true);
+    assert!(ctx.client.is_paused());
+
+    // One approval is not enough, and repeating it does not count twice
+    assert!(!ctx.client.approve_unpause(&guardian));
+    assert!(ctx.client.set_pause(&guardian, &false));
+    assert!(ctx.client.is_paused());
+    let result = ctx.client.try_withdraw(&stream_id, &ctx.receiver);
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+
+    // The quorum-th approval unpauses
+    assert!(ctx.client.approve_unpause(&ctx.admin));
+    assert!(!ctx.client.is_paused());
+
+    // Approvals were cleared: the next pause needs a fresh quorum
+    ctx.client.set_pause(&ctx.admin, &true);
+    assert!(!ctx.client.approve_unpause(&ctx.admin));
+    assert!(ctx.client.is_paused());
+}
+
+#[test]
+fn test_revoked_pauser_approval_does_not_count() {
+    let ctx = setup();
+    let guardian = Address::generate(&ctx.env);
+    let backup = Address::generate(&ctx.env);
+    ctx.client.grant_role(&ctx.admin, &guardian, &Role::Pauser);
+    ctx.client.grant_role(&ctx.admin, &backup, &Role::Pauser);
+    ctx.client.set_unpause_quorum(&ctx.admin, &2);
+    ctx.client.set_pause(&ctx.admin, &true);
+
+    assert!(!ctx.client.approve_unpause(&guardian));
+    ctx.client.revoke_role(&ctx.admin, &guardian, &Role::Pauser);
+
+    // The revoked guardian's approval no longer makes up the quorum
+    assert!(ctx.client.set_pause(&backup, &false));
+    assert!(ctx.client.is_paused());
+
+    assert!(!ctx.client.set_pause(&ctx.admin, &false));
+    assert!(!ctx.client.is_paused());
+}
+
+#[test]
+fn test_approve_unpause_requires_pauser() {
+    let ctx = setup();
+    ctx.client.set_pause(&ctx.admin, &true);
+
+    let result = ctx.client.try_approve_unpause(&ctx.sender);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    let result = ctx.client.try_set_unpause_quorum(&ctx.admin, &0);
+    assert_eq!(result, Err(Ok(Error::InvalidApprovalThreshold)));
+}
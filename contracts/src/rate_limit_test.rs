@@ -0,0 +1,154 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup_with_balance, TestContext},
+    types::{CurveType, DataKey},
+    StellarStreamContractClient, EXTEND_LEDGERS,
+};
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Address as _, Ledger},
+    token::StellarAssetClient,
+    Address, Env,
+};
+
+const WINDOW: u64 = 3_600;
+
+fn create(
+    env: &Env,
+    client: &StellarStreamContractClient,
+    sender: &Address,
+    token_id: &Address,
+) -> u64 {
+    let now = env.ledger().timestamp();
+    client.create_stream(
+        sender,
+        &Address::generate(env),
+        token_id,
+        &1000,
+        &now,
+        &(now + 500),
+        &CurveType::Linear,
+        &false,
+    )
+}
+
+#[test]
+fn test_unlimited_when_unset() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        token_id,
+        ..
+    } = setup_with_balance(100_000);
+    assert_eq!(client.get_max_streams_per_window(), None);
+
+    for _ in 0..5 {
+        create(&env, &client, &sender, &token_id);
+    }
+}
+
+#[test]
+fn test_creations_allowed_up_to_cap() {
+    let TestContext {
+        env,
+        client,
+        admin,
+        sender,
+        token_id,
+        ..
+    } = setup_with_balance(100_000);
+    client.set_max_streams_per_window(&admin, &Some(3));
+
+    for _ in 0..3 {
+        create(&env, &client, &sender, &token_id);
+    }
+    // The counter lives as long as the instance
+    env.as_contract(&client.address, || {
+        let key = DataKey::CreationCount(sender.clone());
+        assert_eq!(env.storage().persistent().get_ttl(&key), EXTEND_LEDGERS);
+    });
+
+    // The cap is per sender: another sender is unaffected
+    let other = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&other, &1000);
+    create(&env, &client, &other, &token_id);
+}
+
+#[test]
+#[should_panic(expected = "Creation rate limit exceeded")]
+fn test_creation_past_cap_rejected() {
+    let TestContext {
+        env,
+        client,
+        admin,
+        sender,
+        token_id,
+        ..
+    } = setup_with_balance(100_000);
+    client.set_max_streams_per_window(&admin, &Some(2));
+
+    create(&env, &client, &sender, &token_id);
+    create(&env, &client, &sender, &token_id);
+    create(&env, &client, &sender, &token_id);
+}
+
+#[test]
+fn test_creation_allowed_after_window_rolls_over() {
+    let TestContext {
+        env,
+        client,
+        admin,
+        sender,
+        token_id,
+        ..
+    } = setup_with_balance(100_000);
+    client.set_max_streams_per_window(&admin, &Some(2));
+
+    create(&env, &client, &sender, &token_id);
+    create(&env, &client, &sender, &token_id);
+
+    env.ledger().with_mut(|li| li.timestamp = WINDOW - 1);
+    let result = client.try_create_stream(
+        &sender,
+        &Address::generate(&env),
+        &token_id,
+        &1000,
+        &(WINDOW - 1),
+        &(WINDOW + 499),
+        &CurveType::Linear,
+        &false,
+    );
+    assert!(result.is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = WINDOW);
+    create(&env, &client, &sender, &token_id);
+    create(&env, &client, &sender, &token_id);
+}
+
+#[test]
+fn test_only_admin_sets_cap() {
+    let TestContext { client, sender, .. } = setup_with_balance(100_000);
+    let result = client.try_set_max_streams_per_window(&sender, &Some(1));
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_clearing_cap_lifts_limit() {
+    let TestContext {
+        env,
+        client,
+        admin,
+        sender,
+        token_id,
+        ..
+    } = setup_with_balance(100_000);
+    client.set_max_streams_per_window(&admin, &Some(1));
+    create(&env, &client, &sender, &token_id);
+
+    client.set_max_streams_per_window(&admin, &None);
+    assert_eq!(client.get_max_streams_per_window(), None);
+    create(&env, &client, &sender, &token_id);
+    create(&env, &client, &sender, &token_id);
+}
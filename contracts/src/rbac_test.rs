@@ -1,17 +1,17 @@
 #![cfg(test)]
 
-use crate::{Role, StellarStream, StellarStreamClient};
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use crate::{errors::Error, types::Role, StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
 
-fn setup_test() -> (Env, Address, StellarStreamClient<'static>) {
+fn setup_test() -> (Env, Address, StellarStreamContractClient<'static>) {
     let env = Env::default();
     env.mock_all_auths();
 
     let admin = Address::generate(&env);
 
     // Deploy contract
-    let contract_id = env.register(StellarStream, ());
-    let client = StellarStreamClient::new(&env, &contract_id);
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
 
     // Initialize with admin (grants all roles)
     client.initialize(&admin);
@@ -21,7 +21,7 @@ fn setup_test() -> (Env, Address, StellarStreamClient<'static>) {
 
 #[test]
 fn test_initialize_grants_all_roles() {
-    let (env, admin, client) = setup_test();
+    let (_env, admin, client) = setup_test();
 
     // Verify admin has all roles
     assert!(client.check_role(&admin, &Role::Admin));
@@ -29,6 +29,19 @@ fn test_initialize_grants_all_roles() {
     assert!(client.check_role(&admin, &Role::TreasuryManager));
 }
 
+#[test]
+fn test_initialize_only_once() {
+    let (env, admin, client) = setup_test();
+
+    let intruder = Address::generate(&env);
+    assert_eq!(
+        client.try_initialize(&intruder),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert!(!client.check_role(&intruder, &Role::Admin));
+    assert_eq!(client.get_admin(), admin);
+}
+
 #[test]
 fn test_admin_can_grant_roles() {
     let (env, admin, client) = setup_test();
@@ -64,19 +77,21 @@ fn test_admin_can_revoke_roles() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only Admin can grant roles")]
 fn test_non_admin_cannot_grant_roles() {
-    let (env, admin, client) = setup_test();
+    let (env, _admin, client) = setup_test();
 
     let non_admin = Address::generate(&env);
     let target = Address::generate(&env);
 
     // Non-admin tries to grant role - should fail
-    client.grant_role(&non_admin, &target, &Role::Pauser);
+    assert_eq!(
+        client.try_grant_role(&non_admin, &target, &Role::Pauser),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert!(!client.check_role(&target, &Role::Pauser));
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only Admin can revoke roles")]
 fn test_non_admin_cannot_revoke_roles() {
     let (env, admin, client) = setup_test();
 
@@ -87,7 +102,11 @@ fn test_non_admin_cannot_revoke_roles() {
     client.grant_role(&admin, &pauser, &Role::Pauser);
 
     // Non-admin tries to revoke - should fail
-    client.revoke_role(&non_admin, &pauser, &Role::Pauser);
+    assert_eq!(
+        client.try_revoke_role(&non_admin, &pauser, &Role::Pauser),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert!(client.check_role(&pauser, &Role::Pauser));
 }
 
 #[test]
@@ -105,14 +124,16 @@ fn test_pauser_can_pause() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only Pauser can pause/unpause")]
 fn test_non_pauser_cannot_pause() {
-    let (env, admin, client) = setup_test();
+    let (env, _admin, client) = setup_test();
 
     let non_pauser = Address::generate(&env);
 
     // Non-pauser tries to pause - should fail
-    client.set_pause(&non_pauser, &true);
+    assert_eq!(
+        client.try_set_pause(&non_pauser, &true),
+        Err(Ok(Error::Unauthorized))
+    );
 }
 
 #[test]
@@ -124,17 +145,38 @@ fn test_treasury_manager_can_update_fee() {
 
     // Treasury manager can update fee
     client.update_fee(&treasury_manager, &200);
+    assert_eq!(client.get_fee_bps(), 200);
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only TreasuryManager can update fee")]
 fn test_non_treasury_manager_cannot_update_fee() {
-    let (env, admin, client) = setup_test();
+    let (env, _admin, client) = setup_test();
 
     let non_manager = Address::generate(&env);
 
     // Non-manager tries to update fee - should fail
-    client.update_fee(&non_manager, &200);
+    assert_eq!(
+        client.try_update_fee(&non_manager, &200),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_initialize_fee(&non_manager, &200, &non_manager),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_fee_cap_is_typed() {
+    let (env, admin, client) = setup_test();
+
+    assert_eq!(
+        client.try_initialize_fee(&admin, &1001, &Address::generate(&env)),
+        Err(Ok(Error::FeeTooHigh))
+    );
+    assert_eq!(
+        client.try_update_fee(&admin, &1001),
+        Err(Ok(Error::FeeTooHigh))
+    );
 }
 
 #[test]
@@ -151,19 +193,20 @@ fn test_treasury_manager_can_update_treasury() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only TreasuryManager can update treasury")]
 fn test_non_treasury_manager_cannot_update_treasury() {
-    let (env, admin, client) = setup_test();
+    let (env, _admin, client) = setup_test();
 
     let non_manager = Address::generate(&env);
     let new_treasury = Address::generate(&env);
 
     // Non-manager tries to update treasury - should fail
-    client.update_treasury(&non_manager, &new_treasury);
+    assert_eq!(
+        client.try_update_treasury(&non_manager, &new_treasury),
+        Err(Ok(Error::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only TreasuryManager can update fee")]
 fn test_pauser_cannot_update_fees() {
     let (env, admin, client) = setup_test();
 
@@ -173,12 +216,14 @@ fn test_pauser_cannot_update_fees() {
     // Pauser can pause
     client.set_pause(&pauser, &true);
 
-    // But pauser cannot update fees (should panic)
-    client.update_fee(&pauser, &200);
+    // But pauser cannot update fees
+    assert_eq!(
+        client.try_update_fee(&pauser, &200),
+        Err(Ok(Error::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only Pauser can pause/unpause")]
 fn test_treasury_manager_cannot_pause() {
     let (env, admin, client) = setup_test();
 
@@ -188,8 +233,11 @@ fn test_treasury_manager_cannot_pause() {
     // Treasury manager can update fees
     client.update_fee(&treasury_manager, &200);
 
-    // But cannot pause (should panic)
-    client.set_pause(&treasury_manager, &true);
+    // But cannot pause
+    assert_eq!(
+        client.try_set_pause(&treasury_manager, &true),
+        Err(Ok(Error::Unauthorized))
+    );
 }
 
 #[test]
@@ -231,29 +279,6 @@ fn test_address_can_have_multiple_roles() {
     client.update_fee(&multi_role, &150);
 }
 
-#[test]
-fn test_role_separation() {
-    let (env, admin, client) = setup_test();
-
-    let pauser = Address::generate(&env);
-    let treasury_manager = Address::generate(&env);
-
-    client.grant_role(&admin, &pauser, &Role::Pauser);
-    client.grant_role(&admin, &treasury_manager, &Role::TreasuryManager);
-
-    // Pauser can pause but not manage treasury
-    client.set_pause(&pauser, &true);
-
-    // Treasury manager can manage fees but not pause
-    client.update_fee(&treasury_manager, &250);
-
-    // Verify role separation
-    assert!(client.check_role(&pauser, &Role::Pauser));
-    assert!(!client.check_role(&pauser, &Role::TreasuryManager));
-    assert!(client.check_role(&treasury_manager, &Role::TreasuryManager));
-    assert!(!client.check_role(&treasury_manager, &Role::Pauser));
-}
-
 #[test]
 fn test_admin_retains_all_permissions() {
     let (env, admin, client) = setup_test();
@@ -264,12 +289,10 @@ fn test_admin_retains_all_permissions() {
 
     let new_admin = Address::generate(&env);
     client.grant_role(&admin, &new_admin, &Role::Admin);
-
-    // All should succeed
+    assert!(client.check_role(&new_admin, &Role::Admin));
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only Pauser can pause/unpause")]
 fn test_revoked_role_loses_permissions() {
     let (env, admin, client) = setup_test();
 
@@ -282,6 +305,21 @@ fn test_revoked_role_loses_permissions() {
     // Revoke role
     client.revoke_role(&admin, &pauser, &Role::Pauser);
 
-    // Cannot pause anymore (should panic)
-    client.set_pause(&pauser, &false);
+    // Cannot unpause anymore
+    assert_eq!(
+        client.try_set_pause(&pauser, &false),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_non_admin_cannot_upgrade() {
+    let (env, _admin, client) = setup_test();
+
+    let non_admin = Address::generate(&env);
+    let new_wasm_hash = BytesN::from_array(&env, &[1u8; 32]);
+    assert_eq!(
+        client.try_upgrade(&non_admin, &new_wasm_hash),
+        Err(Ok(Error::Unauthorized))
+    );
 }
@@ -0,0 +1,112 @@
+#![cfg(test)]
+
+use crate::{
+    test_helpers::{setup, TestContext},
+    types::{CurveType, DataKey},
+};
+use soroban_sdk::{contract, contractimpl, symbol_short, testutils::Ledger, Address, Env};
+
+// Soroban already rejects a contract calling back into itself, so a real re-entrant
+// call never reaches the lock. These tests observe the lock directly instead.
+
+/// Token that records whether the stream contract held its lock while paying out
+#[contract]
+pub struct LockProbeToken;
+
+#[contractimpl]
+impl LockProbeToken {
+    pub fn watch(env: Env, stream_contract: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("watched"), &stream_contract);
+    }
+
+    pub fn transfer(env: Env, from: Address, _to: Address, _amount: i128) {
+        let watched: Option<Address> = env.storage().instance().get(&symbol_short!("watched"));
+        if watched != Some(from.clone()) {
+            return;
+        }
+        let locked = env.as_contract(&from, || {
+            env.storage()
+                .temporary()
+                .get(&DataKey::ReentrancyLock)
+                .unwrap_or(false)
+        });
+        env.storage()
+            .instance()
+            .set(&symbol_short!("locked"), &locked);
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+
+    /// Lock state seen by the latest payout, cleared on read
+    pub fn take_locked(env: Env) -> Option<bool> {
+        let locked = env.storage().instance().get(&symbol_short!("locked"));
+        env.storage().instance().remove(&symbol_short!("locked"));
+        locked
+    }
+}
+
+fn is_locked(ctx: &TestContext) -> bool {
+    ctx.env.as_contract(&ctx.client.address, || {
+        ctx.env.storage().temporary().has(&DataKey::ReentrancyLock)
+    })
+}
+
+#[test]
+fn test_payouts_hold_the_lock() {
+    let ctx = setup();
+    let token_id = ctx.env.register(LockProbeToken, ());
+    let token = LockProbeTokenClient::new(&ctx.env, &token_id);
+    let create = || {
+        ctx.client.create_stream(
+            &ctx.sender,
+            &ctx.receiver,
+            &token_id,
+            &1000,
+            &0,
+            &1000,
+            &CurveType::Linear,
+            &false,
+        )
+    };
+    let first = create();
+    let second = create();
+    token.watch(&ctx.client.address);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 400);
+    assert_eq!(ctx.client.withdraw(&first, &ctx.receiver), 400);
+    assert_eq!(token.take_locked(), Some(true));
+    assert!(!is_locked(&ctx));
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(ctx.client.withdraw_all(&ctx.receiver, &0, &10), 600);
+    assert_eq!(token.take_locked(), Some(true));
+    assert!(!is_locked(&ctx));
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(ctx.client.finalize_stream(&second), 500);
+    assert_eq!(token.take_locked(), Some(true));
+    assert!(!is_locked(&ctx));
+}
+
+#[test]
+fn test_payouts_refuse_to_start_while_locked() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+    ctx.env.as_contract(&ctx.client.address, || {
+        ctx.env
+            .storage()
+            .temporary()
+            .set(&DataKey::ReentrancyLock, &true);
+    });
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert!(ctx.client.try_withdraw(&stream_id, &ctx.receiver).is_err());
+    assert!(ctx.client.try_withdraw_all(&ctx.receiver, &0, &10).is_err());
+    assert!(ctx.client.try_finalize_stream(&stream_id).is_err());
+    assert!(ctx.client.try_cancel(&stream_id, &ctx.sender).is_err());
+    assert_eq!(ctx.token.balance(&ctx.receiver), 0);
+}
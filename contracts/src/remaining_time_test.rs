@@ -76,3 +76,99 @@ fn test_remaining_time_stream_not_found() {
     let result = client.try_get_stream_remaining_time(&999);
     assert_eq!(result, Err(Ok(Error::StreamNotFound)));
 }
+
+#[test]
+fn test_remaining_time_before_start_is_full_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+
+    let sender = Address::generate(&env);
+    let receiver = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token_id).mint(&sender, &1000);
+
+    // Stream: starts at 1000, ends at 1500
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &1000,
+        &1500,
+        &CurveType::Linear,
+        &false,
+    );
+
+    // Well before start: full window, not end_time - now
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    assert_eq!(client.get_stream_remaining_time(&stream_id), 500);
+
+    // Exactly at start
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(client.get_stream_remaining_time(&stream_id), 500);
+
+    // Midpoint
+    env.ledger().with_mut(|li| li.timestamp = 1250);
+    assert_eq!(client.get_stream_remaining_time(&stream_id), 250);
+
+    // Far past the end never underflows
+    env.ledger().with_mut(|li| li.timestamp = u64::MAX);
+    assert_eq!(client.get_stream_remaining_time(&stream_id), 0);
+}
+
+#[test]
+fn test_remaining_time_frozen_while_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+
+    let sender = Address::generate(&env);
+    let receiver = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token_id).mint(&sender, &1000);
+
+    // Stream: starts at 0, ends at 1000
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+
+    // Pause at the midpoint
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    client.pause_stream(&stream_id, &sender);
+    assert_eq!(client.get_stream_remaining_time(&stream_id), 500);
+
+    // Time passes while paused: the countdown does not move
+    env.ledger().with_mut(|li| li.timestamp = 800);
+    assert_eq!(client.get_stream_remaining_time(&stream_id), 500);
+    env.ledger().with_mut(|li| li.timestamp = 1200);
+    assert_eq!(client.get_stream_remaining_time(&stream_id), 500);
+
+    // After resuming it counts down again from where it stopped
+    client.unpause_stream(&stream_id, &sender);
+    assert_eq!(client.get_stream_remaining_time(&stream_id), 500);
+    env.ledger().with_mut(|li| li.timestamp = 1300);
+    assert_eq!(client.get_stream_remaining_time(&stream_id), 400);
+
+    // It reaches zero exactly when everything has unlocked
+    env.ledger().with_mut(|li| li.timestamp = 1700);
+    assert_eq!(client.get_stream_remaining_time(&stream_id), 0);
+    assert_eq!(client.get_unlocked(&stream_id), 1000);
+}
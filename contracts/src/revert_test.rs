@@ -0,0 +1,169 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup, TestContext},
+    types::{CurveType, StreamOptions},
+    StellarStreamContractClient,
+};
+use soroban_sdk::{testutils::Ledger, Address};
+
+fn create(
+    client: &StellarStreamContractClient,
+    sender: &Address,
+    receiver: &Address,
+    token_id: &Address,
+    revert_after: Option<u64>,
+) -> Result<u64, Error> {
+    match client.try_create_stream_with_options(
+        sender,
+        receiver,
+        token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &StreamOptions {
+            is_soulbound: false,
+            idempotency_key: None,
+            mutual_cancel: false,
+            revert_after,
+            on_cancel_hook: false,
+        },
+    ) {
+        Ok(id) => Ok(id.unwrap()),
+        Err(Ok(err)) => Err(err),
+        Err(Err(_)) => panic!("unexpected host error"),
+    }
+}
+
+#[test]
+fn test_revert_unclaimed_after_deadline() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        token,
+        ..
+    } = setup();
+    let stream_id = create(&client, &sender, &receiver, &token_id, Some(2000)).unwrap();
+
+    // The receiver claimed only part of the stream
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    client.withdraw(&stream_id, &receiver);
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    assert_eq!(client.revert_unclaimed(&stream_id, &sender), 700);
+
+    assert_eq!(token.balance(&sender), 9_700);
+    assert_eq!(token.balance(&receiver), 300);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(client.check_solvency(&token_id), (0, 0));
+    assert!(!client.stream_exists(&stream_id));
+}
+
+#[test]
+fn test_revert_rejected_before_deadline() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let stream_id = create(&client, &sender, &receiver, &token_id, Some(2000)).unwrap();
+
+    env.ledger().with_mut(|li| li.timestamp = 1999);
+    let result = client.try_revert_unclaimed(&stream_id, &sender);
+    assert_eq!(result, Err(Ok(Error::RevertNotAvailable)));
+
+    // Only the sender may revert
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    let result = client.try_revert_unclaimed(&stream_id, &receiver);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_revert_requires_deadline() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let stream_id = create(&client, &sender, &receiver, &token_id, None).unwrap();
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+    let result = client.try_revert_unclaimed(&stream_id, &sender);
+    assert_eq!(result, Err(Ok(Error::RevertNotAvailable)));
+
+    // A deadline before the stream ends is rejected at creation
+    let result = create(&client, &sender, &receiver, &token_id, Some(999));
+    assert_eq!(result, Err(Error::InvalidTimeRange));
+}
+
+#[test]
+fn test_top_up_cannot_move_end_past_deadline() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        token,
+        ..
+    } = setup();
+    let stream_id = create(&client, &sender, &receiver, &token_id, Some(1000)).unwrap();
+
+    // Doubling the stream would end it at 2000, after the deadline
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    let result = client.try_top_up_stream(&stream_id, &sender, &1000);
+    assert_eq!(result, Err(Ok(Error::InvalidTimeRange)));
+    assert_eq!(client.get_stream(&stream_id).end_time, 1000);
+    assert_eq!(token.balance(&sender), 9_000);
+
+    // With room before the deadline the top-up goes through
+    let stream_id = create(&client, &sender, &receiver, &token_id, Some(2000)).unwrap();
+    client.top_up_stream(&stream_id, &sender, &1000);
+    assert_eq!(client.get_stream(&stream_id).end_time, 2000);
+}
+
+#[test]
+fn test_revert_waits_for_pause_adjusted_end() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        token,
+        ..
+    } = setup();
+    let stream_id = create(&client, &sender, &receiver, &token_id, Some(1000)).unwrap();
+
+    // Paused for 100 seconds, so the stream now ends at 1100
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.pause_stream(&stream_id, &sender);
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.unpause_stream(&stream_id, &sender);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let result = client.try_revert_unclaimed(&stream_id, &sender);
+    assert_eq!(result, Err(Ok(Error::StreamNotEnded)));
+
+    // A stream that is still paused never reaches its end
+    client.pause_stream(&stream_id, &sender);
+    env.ledger().with_mut(|li| li.timestamp = 5000);
+    let result = client.try_revert_unclaimed(&stream_id, &sender);
+    assert_eq!(result, Err(Ok(Error::StreamNotEnded)));
+
+    client.unpause_stream(&stream_id, &sender);
+    env.ledger().with_mut(|li| li.timestamp = 5100);
+    assert_eq!(client.revert_unclaimed(&stream_id, &sender), 1000);
+    assert_eq!(token.balance(&sender), 10_000);
+}
@@ -0,0 +1,124 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup, TestContext},
+    types::Role,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    Address,
+};
+
+#[test]
+fn test_roles_default_to_admin() {
+    let TestContext { client, .. } = setup();
+
+    assert_eq!(client.get_role_admin(&Role::Pauser), Role::Admin);
+    assert_eq!(client.get_role_admin(&Role::Admin), Role::Admin);
+}
+
+#[test]
+fn test_delegated_manager_grants_subordinate_role() {
+    let TestContext {
+        env, client, admin, ..
+    } = setup();
+
+    let officer = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    client.grant_role(&admin, &officer, &Role::ComplianceOfficer);
+    client.set_role_admin(&admin, &Role::Pauser, &Role::ComplianceOfficer);
+
+    client.grant_role(&officer, &guardian, &Role::Pauser);
+    assert!(client.check_role(&guardian, &Role::Pauser));
+
+    client.revoke_role(&officer, &guardian, &Role::Pauser);
+    assert!(!client.check_role(&guardian, &Role::Pauser));
+
+    // Admin keeps control over delegated roles
+    client.grant_role(&admin, &guardian, &Role::Pauser);
+    assert!(client.check_role(&guardian, &Role::Pauser));
+}
+
+#[test]
+fn test_delegated_manager_cannot_grant_other_roles() {
+    let TestContext {
+        env, client, admin, ..
+    } = setup();
+
+    let officer = Address::generate(&env);
+    client.grant_role(&admin, &officer, &Role::ComplianceOfficer);
+    client.set_role_admin(&admin, &Role::Pauser, &Role::ComplianceOfficer);
+
+    assert_eq!(
+        client.try_grant_role(&officer, &officer, &Role::Admin),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_grant_role(&officer, &officer, &Role::TreasuryManager),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert!(!client.check_role(&officer, &Role::Admin));
+
+    // Revoking outside the delegated role is rejected too
+    assert_eq!(
+        client.try_revoke_role(&officer, &admin, &Role::Admin),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert!(client.check_role(&admin, &Role::Admin));
+}
+
+#[test]
+fn test_set_role_admin_requires_admin() {
+    let TestContext { env, client, .. } = setup();
+
+    let result = client.try_set_role_admin(
+        &Address::generate(&env),
+        &Role::Pauser,
+        &Role::ComplianceOfficer,
+    );
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_admin_role_cannot_be_delegated() {
+    let TestContext {
+        env, client, admin, ..
+    } = setup();
+
+    let officer = Address::generate(&env);
+    client.grant_role(&admin, &officer, &Role::ComplianceOfficer);
+    assert_eq!(
+        client.try_set_role_admin(&admin, &Role::Admin, &Role::ComplianceOfficer),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(client.get_role_admin(&Role::Admin), Role::Admin);
+    assert_eq!(
+        client.try_grant_role(&officer, &officer, &Role::Admin),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_role_events_only_on_state_change() {
+    let TestContext {
+        env, client, admin, ..
+    } = setup();
+    let guardian = Address::generate(&env);
+
+    client.grant_role(&admin, &guardian, &Role::Pauser);
+    assert_eq!(env.events().all().len(), 1);
+
+    // Granting a held role is a silent no-op
+    client.grant_role(&admin, &guardian, &Role::Pauser);
+    assert_eq!(env.events().all().len(), 0);
+    assert!(client.check_role(&guardian, &Role::Pauser));
+
+    client.revoke_role(&admin, &guardian, &Role::Pauser);
+    assert_eq!(env.events().all().len(), 1);
+
+    // Revoking a role the account lacks is a silent no-op
+    client.revoke_role(&admin, &guardian, &Role::Pauser);
+    assert_eq!(env.events().all().len(), 0);
+    assert!(!client.check_role(&guardian, &Role::Pauser));
+}
@@ -0,0 +1,102 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup_with_balance, TestContext},
+    types::CurveType,
+};
+
+#[test]
+fn test_linear_schedule_is_straight_line() {
+    let TestContext {
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup_with_balance(100_000);
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &10_000,
+        &100,
+        &1100,
+        &CurveType::Linear,
+        &false,
+    );
+
+    let points = client.get_unlock_schedule(&stream_id, &11);
+    assert_eq!(points.len(), 11);
+    assert_eq!(points.first().unwrap(), (100, 0));
+    assert_eq!(points.last().unwrap(), (1100, 10_000));
+
+    for (i, (timestamp, unlocked)) in points.iter().enumerate() {
+        let i = i as u64;
+        assert_eq!(timestamp, 100 + i * 100);
+        assert_eq!(unlocked, (i * 1000) as i128);
+    }
+}
+
+#[test]
+fn test_exponential_schedule_endpoints_and_shape() {
+    let TestContext {
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup_with_balance(100_000);
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &10_000,
+        &0,
+        &1000,
+        &CurveType::Exponential,
+        &false,
+    );
+
+    let points = client.get_unlock_schedule(&stream_id, &3);
+    assert_eq!(points.get(0).unwrap(), (0, 0));
+    // Quadratic curve: a quarter of the total at the halfway point
+    assert_eq!(points.get(1).unwrap(), (500, 2_500));
+    assert_eq!(points.get(2).unwrap(), (1000, 10_000));
+}
+
+#[test]
+fn test_schedule_samples_are_clamped() {
+    let TestContext {
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup_with_balance(100_000);
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &10_000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+
+    assert_eq!(client.get_unlock_schedule(&stream_id, &1_000).len(), 100);
+
+    let points = client.get_unlock_schedule(&stream_id, &0);
+    assert_eq!(points.len(), 2);
+    assert_eq!(points.get(0).unwrap(), (0, 0));
+    assert_eq!(points.get(1).unwrap(), (1000, 10_000));
+}
+
+#[test]
+fn test_schedule_stream_not_found() {
+    let TestContext { client, .. } = setup_with_balance(100_000);
+
+    let result = client.try_get_unlock_schedule(&999, &10);
+    assert_eq!(result, Err(Ok(Error::StreamNotFound)));
+}
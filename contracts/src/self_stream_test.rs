@@ -0,0 +1,78 @@
+#![cfg(test)]
+
+use crate::{
+    test_helpers::{setup, TestContext},
+    types::{CurveType, StreamOptions},
+};
+use soroban_sdk::testutils::Ledger;
+
+#[test]
+fn test_self_stream_withdraw_then_cancel_loses_nothing() {
+    let TestContext {
+        env,
+        client,
+        sender: founder,
+        token_id,
+        token,
+        ..
+    } = setup();
+
+    let stream_id = client.create_stream(
+        &founder,
+        &founder,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(token.balance(&founder), 9_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    assert_eq!(client.withdraw(&stream_id, &founder), 400);
+    assert_eq!(token.balance(&founder), 9_400);
+
+    env.ledger().with_mut(|li| li.timestamp = 700);
+    client.cancel(&stream_id, &founder);
+
+    // Everything is back with the founder and nothing is stranded
+    assert_eq!(token.balance(&founder), 10_000);
+    assert_eq!(token.balance(&client.address), 0);
+    assert!(client.get_stream(&stream_id).cancelled);
+}
+
+#[test]
+fn test_mutual_cancel_self_stream_needs_one_signature() {
+    let TestContext {
+        env,
+        client,
+        sender: founder,
+        token_id,
+        token,
+        ..
+    } = setup();
+
+    let stream_id = client.create_stream_with_options(
+        &founder,
+        &founder,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &StreamOptions {
+            is_soulbound: false,
+            idempotency_key: None,
+            mutual_cancel: true,
+            revert_after: None,
+            on_cancel_hook: false,
+        },
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 250);
+    client.cancel(&stream_id, &founder);
+
+    assert_eq!(token.balance(&founder), 10_000);
+    assert_eq!(token.balance(&client.address), 0);
+}
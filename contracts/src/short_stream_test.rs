@@ -0,0 +1,158 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup, TestContext},
+    types::CurveType,
+};
+use soroban_sdk::testutils::Ledger;
+
+#[test]
+fn test_one_second_stream_unlocks_all_at_end() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        token,
+        ..
+    } = setup();
+
+    for curve in [CurveType::Linear, CurveType::Exponential] {
+        let stream_id = client.create_stream(
+            &sender, &receiver, &token_id, &1001, &100, &101, &curve, &false,
+        );
+
+        env.ledger().with_mut(|li| li.timestamp = 100);
+        assert_eq!(client.get_unlocked(&stream_id), 0);
+
+        env.ledger().with_mut(|li| li.timestamp = 101);
+        assert_eq!(client.get_unlocked(&stream_id), 1001);
+        assert_eq!(client.withdraw(&stream_id, &receiver), 1001);
+
+        env.ledger().with_mut(|li| li.timestamp = 0);
+    }
+
+    assert_eq!(token.balance(&receiver), 2002);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_two_second_stream_strands_nothing() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        token,
+        ..
+    } = setup();
+
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1001,
+        &0,
+        &2,
+        &CurveType::Linear,
+        &false,
+    );
+
+    // Odd amount: the midpoint rounds down, the end releases the remainder
+    env.ledger().with_mut(|li| li.timestamp = 1);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 500);
+
+    env.ledger().with_mut(|li| li.timestamp = 2);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 501);
+
+    assert_eq!(token.balance(&receiver), 1001);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_two_second_stream_cancel_splits_exactly() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        token,
+        ..
+    } = setup();
+
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1001,
+        &0,
+        &2,
+        &CurveType::Linear,
+        &false,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1);
+    client.cancel(&stream_id, &sender);
+
+    assert_eq!(token.balance(&receiver), 500);
+    assert_eq!(token.balance(&sender), 10_000 - 500);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_min_duration_guard() {
+    let TestContext {
+        client,
+        admin,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+
+    assert_eq!(
+        client.try_set_min_duration(&sender, &Some(60)),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.set_min_duration(&admin, &Some(60));
+
+    let result = client.try_create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &59,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidTimeRange)));
+
+    client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &60,
+        &CurveType::Linear,
+        &false,
+    );
+
+    // Clearing the floor allows one-second streams again
+    client.set_min_duration(&admin, &None);
+    client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1,
+        &CurveType::Linear,
+        &false,
+    );
+}
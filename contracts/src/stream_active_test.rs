@@ -127,3 +127,31 @@ fn test_paused_stream_returns_false() {
 
     assert!(!client.is_stream_active(&stream_id));
 }
+
+#[test]
+fn test_stream_exists() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let (client, sender, receiver, token_id) = setup(&env);
+
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &500,
+        &CurveType::Linear,
+        &false,
+    );
+
+    assert!(client.stream_exists(&stream_id));
+    assert!(!client.stream_exists(&999_u64));
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.cancel(&stream_id, &sender);
+
+    assert!(!client.stream_exists(&stream_id));
+}
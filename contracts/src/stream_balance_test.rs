@@ -0,0 +1,191 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup, TestContext},
+    types::CurveType,
+};
+use soroban_sdk::testutils::Ledger;
+
+#[test]
+fn test_unlocked_plus_locked_equals_amount() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &100,
+        &600,
+        &CurveType::Linear,
+        &false,
+    );
+
+    for (now, expected_unlocked) in [
+        (0, 0),
+        (100, 0),
+        (225, 250),
+        (350, 500),
+        (600, 1000),
+        (900, 1000),
+    ] {
+        env.ledger().with_mut(|li| li.timestamp = now);
+        let unlocked = client.get_unlocked(&stream_id);
+        let locked = client.get_locked(&stream_id);
+        assert_eq!(unlocked, expected_unlocked);
+        assert_eq!(unlocked + locked, 1000);
+    }
+}
+
+#[test]
+fn test_unlocked_ignores_withdrawals() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    client.withdraw(&stream_id, &receiver);
+
+    assert_eq!(client.get_unlocked(&stream_id), 400);
+    assert_eq!(client.get_locked(&stream_id), 600);
+}
+
+#[test]
+fn test_unlocked_locked_stream_not_found() {
+    let TestContext { client, .. } = setup();
+
+    assert_eq!(
+        client.try_get_unlocked(&999),
+        Err(Ok(Error::StreamNotFound))
+    );
+    assert_eq!(client.try_get_locked(&999), Err(Ok(Error::StreamNotFound)));
+}
+
+#[test]
+fn test_accrual_starts_at_start_time_with_no_cliff_gap() {
+    // Streams have no separate cliff: the cliff is effectively `start_time`
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &100,
+        &500,
+        &CurveType::Linear,
+        &false,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.get_unlocked(&stream_id), 0);
+
+    // One second in already accrues, so there is no gap after the start
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    assert_eq!(client.get_unlocked(&stream_id), 2);
+
+    // 25% of the way through
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 250);
+
+    // 50% of the way through
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 250);
+    assert_eq!(client.get_stream(&stream_id).withdrawn_amount, 500);
+}
+
+#[test]
+fn test_solvency_after_create_and_partial_withdraw() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let first = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+    client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &500,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    client.withdraw(&first, &receiver);
+
+    assert_eq!(client.get_token_balance(&token_id), 1200);
+    let (balance, liability) = client.check_solvency(&token_id);
+    assert_eq!(balance, 1200);
+    assert_eq!(liability, 1200);
+    assert!(balance >= liability);
+
+    // Cancelling settles the stream, so both sides drop together
+    client.cancel(&first, &sender);
+    assert_eq!(client.check_solvency(&token_id), (500, 500));
+}
+
+#[test]
+fn test_solvency_tracks_every_payout_path() {
+    let ctx = setup();
+    let first = ctx.create_stream(1000);
+    let second = ctx.create_stream(1000);
+    let solvency = || ctx.client.check_solvency(&ctx.token_id);
+    assert_eq!(solvency(), (2000, 2000));
+
+    ctx.client.top_up_stream(&first, &ctx.sender, &500);
+    assert_eq!(solvency(), (2500, 2500));
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(ctx.client.withdraw_all(&ctx.receiver, &0, &10), 1000);
+    assert_eq!(solvency(), (1500, 1500));
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(ctx.client.finalize_stream(&second), 500);
+    assert_eq!(solvency(), (1000, 1000));
+
+    ctx.client.cancel(&first, &ctx.sender);
+    assert_eq!(solvency(), (0, 0));
+}
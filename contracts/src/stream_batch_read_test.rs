@@ -0,0 +1,65 @@
+#![cfg(test)]
+
+use crate::{
+    test_helpers::{setup, TestContext},
+    types::CurveType,
+};
+use soroban_sdk::{vec, Vec};
+
+#[test]
+fn test_batch_read_aligns_by_index() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let mut ids = Vec::new(&env);
+    for amount in [100_i128, 200, 300] {
+        ids.push_back(client.create_stream(
+            &sender,
+            &receiver,
+            &token_id,
+            &amount,
+            &0,
+            &1000,
+            &CurveType::Linear,
+            &false,
+        ));
+    }
+    client.cancel(&ids.get(1).unwrap(), &sender);
+
+    let query = vec![
+        &env,
+        ids.get(2).unwrap(),
+        999,
+        ids.get(0).unwrap(),
+        ids.get(1).unwrap(),
+    ];
+    let streams = client.get_streams_batch(&query);
+
+    assert_eq!(streams.len(), 4);
+    assert_eq!(streams.get(0).unwrap().unwrap().total_amount, 300);
+    assert!(streams.get(1).unwrap().is_none());
+    assert_eq!(streams.get(2).unwrap().unwrap().total_amount, 100);
+    assert!(streams.get(3).unwrap().is_none());
+}
+
+#[test]
+fn test_batch_read_empty() {
+    let TestContext { env, client, .. } = setup();
+    assert_eq!(client.get_streams_batch(&Vec::new(&env)).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Too many stream ids")]
+fn test_batch_read_rejects_oversized_input() {
+    let TestContext { env, client, .. } = setup();
+    let mut ids = Vec::new(&env);
+    for id in 0..51_u64 {
+        ids.push_back(id);
+    }
+    client.get_streams_batch(&ids);
+}
@@ -0,0 +1,116 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup, TestContext},
+    types::CurveType,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_receiver_pause_freezes_accrual_like_sender_pause() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 200);
+    ctx.client.pause_stream(&stream_id, &ctx.receiver);
+    assert_eq!(
+        ctx.client.get_stream(&stream_id).paused_by,
+        Some(ctx.receiver.clone())
+    );
+
+    // Nothing accrues while paused
+    ctx.env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(ctx.client.get_unlocked(&stream_id), 200);
+
+    ctx.client.unpause_stream(&stream_id, &ctx.receiver);
+    let stream = ctx.client.get_stream(&stream_id);
+    assert!(!stream.is_paused);
+    assert_eq!(stream.paused_by, None);
+    assert_eq!(stream.total_paused_duration, 300);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 600);
+    assert_eq!(ctx.client.withdraw(&stream_id, &ctx.receiver), 300);
+}
+
+#[test]
+fn test_only_pausing_party_or_admin_can_resume() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+
+    ctx.client.pause_stream(&stream_id, &ctx.receiver);
+    assert_eq!(
+        ctx.client.try_unpause_stream(&stream_id, &ctx.sender),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // An admin can override a hold placed by either party
+    ctx.client.unpause_stream(&stream_id, &ctx.admin);
+    assert!(!ctx.client.get_stream(&stream_id).is_paused);
+
+    ctx.client.pause_stream(&stream_id, &ctx.sender);
+    assert_eq!(
+        ctx.client.try_unpause_stream(&stream_id, &ctx.receiver),
+        Err(Ok(Error::Unauthorized))
+    );
+    ctx.client.unpause_stream(&stream_id, &ctx.sender);
+}
+
+#[test]
+fn test_unrelated_address_cannot_pause_or_resume() {
+    let ctx = setup();
+    let stream_id = ctx.create_stream(1000);
+    let stranger = Address::generate(&ctx.env);
+
+    assert_eq!(
+        ctx.client.try_pause_stream(&stream_id, &stranger),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    ctx.client.pause_stream(&stream_id, &ctx.sender);
+    assert_eq!(
+        ctx.client.try_unpause_stream(&stream_id, &stranger),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_pause_before_start_never_underflows() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &100,
+        &1100,
+        &CurveType::Linear,
+        &false,
+    );
+
+    // Paused at 50, queried well after the start: `now` is past the start but the
+    // effective time is still before it
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    client.pause_stream(&stream_id, &sender);
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    assert_eq!(client.get_unlocked(&stream_id), 0);
+    assert_eq!(client.get_stream_remaining_time(&stream_id), 1000);
+    assert_eq!(
+        client.try_withdraw(&stream_id, &receiver),
+        Err(Ok(Error::StreamPaused))
+    );
+
+    client.cancel(&stream_id, &sender);
+    assert_eq!(client.get_stream(&stream_id).withdrawn_amount, 0);
+}
@@ -0,0 +1,113 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup_with_balance, TestContext},
+    types::CurveType,
+};
+use soroban_sdk::testutils::Ledger;
+
+#[test]
+fn test_linear_stream_rate() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup_with_balance(100_000);
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &10_000,
+        &100,
+        &1_100,
+        &CurveType::Linear,
+        &false,
+    );
+
+    // Not flowing yet
+    assert_eq!(client.get_stream_rate(&stream_id), 0);
+
+    for now in [100, 500, 1_099] {
+        env.ledger().with_mut(|li| li.timestamp = now);
+        assert_eq!(client.get_stream_rate(&stream_id), 10);
+    }
+
+    // Ended
+    env.ledger().with_mut(|li| li.timestamp = 1_100);
+    assert_eq!(client.get_stream_rate(&stream_id), 0);
+}
+
+#[test]
+fn test_rate_truncates_and_uses_average_for_curves() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup_with_balance(100_000);
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1_000,
+        &0,
+        &300,
+        &CurveType::Exponential,
+        &false,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 150);
+    assert_eq!(client.get_stream_rate(&stream_id), 3);
+}
+
+#[test]
+fn test_rate_while_paused_and_after_resume() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup_with_balance(100_000);
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &10_000,
+        &0,
+        &1_000,
+        &CurveType::Linear,
+        &false,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    client.pause_stream(&stream_id, &sender);
+    assert_eq!(client.get_stream_rate(&stream_id), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 600);
+    client.unpause_stream(&stream_id, &sender);
+    assert_eq!(client.get_stream_rate(&stream_id), 10);
+
+    // The 200s pause pushed the end back to 1_200
+    env.ledger().with_mut(|li| li.timestamp = 1_100);
+    assert_eq!(client.get_stream_rate(&stream_id), 10);
+    env.ledger().with_mut(|li| li.timestamp = 1_200);
+    assert_eq!(client.get_stream_rate(&stream_id), 0);
+}
+
+#[test]
+fn test_rate_stream_not_found() {
+    let TestContext { client, .. } = setup_with_balance(100_000);
+
+    assert_eq!(
+        client.try_get_stream_rate(&999),
+        Err(Ok(Error::StreamNotFound))
+    );
+}
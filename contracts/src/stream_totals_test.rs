@@ -0,0 +1,94 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup, TestContext},
+    types::CurveType,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_withdrawn_amount_tracks_each_withdrawal() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(client.get_withdrawn_amount(&stream_id), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 250);
+    client.withdraw(&stream_id, &receiver);
+    assert_eq!(client.get_withdrawn_amount(&stream_id), 250);
+
+    env.ledger().with_mut(|li| li.timestamp = 600);
+    client.withdraw(&stream_id, &receiver);
+    assert_eq!(client.get_withdrawn_amount(&stream_id), 600);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.withdraw(&stream_id, &receiver);
+    assert_eq!(client.get_withdrawn_amount(&stream_id), 1000);
+
+    // Withdrawals never change what was deposited
+    assert_eq!(client.get_deposited_amount(&stream_id), 1000);
+}
+
+#[test]
+fn test_deposited_amount_is_post_fee_principal() {
+    let TestContext {
+        env,
+        client,
+        admin,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    client.initialize_fee(&admin, &100, &Address::generate(&env));
+
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+
+    // 1% creation fee is skimmed before the principal is recorded
+    assert_eq!(client.get_deposited_amount(&stream_id), 990);
+
+    client.top_up_stream(&stream_id, &sender, &500);
+    assert_eq!(client.get_deposited_amount(&stream_id), 1490);
+}
+
+#[test]
+fn test_totals_stream_not_found() {
+    let TestContext { client, .. } = setup();
+
+    assert_eq!(
+        client.try_get_withdrawn_amount(&999),
+        Err(Ok(Error::StreamNotFound))
+    );
+    assert_eq!(
+        client.try_get_deposited_amount(&999),
+        Err(Ok(Error::StreamNotFound))
+    );
+}
@@ -0,0 +1,100 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup, TestContext},
+    types::{CurveParams, CurveType},
+};
+use soroban_sdk::{testutils::Ledger, Vec};
+
+#[test]
+fn test_subscription_releases_one_period_at_a_time() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        token,
+        ..
+    } = setup();
+
+    // 4 payments of 100, one every 30 seconds starting at t=10
+    let stream_id = client.create_subscription(&sender, &receiver, &token_id, &100, &30, &4, &10);
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.total_amount, 400);
+    assert_eq!(stream.end_time, 130);
+    assert_eq!(stream.curve_type, CurveType::Step);
+    assert_eq!(stream.curve_params.step_interval, 30);
+    assert_eq!(token.balance(&sender), 9_600);
+    assert_eq!(client.get_next_release_time(&stream_id), 40);
+
+    // Nothing until the first period ends
+    env.ledger().with_mut(|li| li.timestamp = 39);
+    let result = client.try_withdraw(&stream_id, &receiver);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+
+    for (boundary, next) in [(40, 70), (70, 100), (100, 130), (130, 130)] {
+        env.ledger().with_mut(|li| li.timestamp = boundary);
+        assert_eq!(client.withdraw(&stream_id, &receiver), 100);
+        assert_eq!(client.get_next_release_time(&stream_id), next);
+
+        // Still mid-period: nothing more to take
+        env.ledger().with_mut(|li| li.timestamp = boundary + 29);
+        if boundary < 130 {
+            let result = client.try_withdraw(&stream_id, &receiver);
+            assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+        }
+    }
+    assert_eq!(token.balance(&receiver), 400);
+}
+
+#[test]
+fn test_subscription_validation() {
+    let TestContext {
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+
+    let result = client.try_create_subscription(&sender, &receiver, &token_id, &100, &0, &4, &0);
+    assert_eq!(result, Err(Ok(Error::InvalidTimeRange)));
+
+    let result = client.try_create_subscription(&sender, &receiver, &token_id, &0, &30, &4, &0);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+    let result =
+        client.try_create_subscription(&sender, &receiver, &token_id, &100, &u64::MAX, &2, &0);
+    assert_eq!(result, Err(Ok(Error::InvalidTimeRange)));
+}
+
+#[test]
+fn test_step_interval_validation() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+
+    for interval in [0, 1001] {
+        let result = client.try_create_stream_with_curve(
+            &sender,
+            &receiver,
+            &token_id,
+            &1000,
+            &0,
+            &1000,
+            &CurveType::Step,
+            &CurveParams {
+                checkpoints: Vec::new(&env),
+                step_interval: interval,
+            },
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidCurve)));
+    }
+}
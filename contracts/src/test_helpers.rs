@@ -0,0 +1,75 @@
+#![cfg(test)]
+
+use crate::{types::CurveType, StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+/// Fixture shared by the contract's unit tests
+pub(crate) struct TestContext<'a> {
+    pub env: Env,
+    pub client: StellarStreamContractClient<'a>,
+    pub admin: Address,
+    pub sender: Address,
+    pub receiver: Address,
+    pub token_id: Address,
+    pub token: TokenClient<'a>,
+}
+
+/// Initialized contract with all auths mocked and the clock at 0; `sender` holds
+/// 10_000 of a fresh token.
+pub(crate) fn setup() -> TestContext<'static> {
+    setup_with_balance(10_000)
+}
+
+/// Same as `setup`, with `sender` holding `balance` instead
+pub(crate) fn setup_with_balance(balance: i128) -> TestContext<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let receiver = Address::generate(&env);
+    let token = funded_token(&env, &sender, balance);
+    client.initialize(&admin);
+
+    TestContext {
+        token_id: token.address.clone(),
+        env,
+        client,
+        admin,
+        sender,
+        receiver,
+        token,
+    }
+}
+
+/// Registers a new token and mints `amount` of it to `holder`
+pub(crate) fn funded_token(env: &Env, holder: &Address, amount: i128) -> TokenClient<'static> {
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    StellarAssetClient::new(env, &token_id).mint(holder, &amount);
+    TokenClient::new(env, &token_id)
+}
+
+impl TestContext<'_> {
+    /// Linear stream of `amount` from `sender` to `receiver` over [0, 1000]
+    pub fn create_stream(&self, amount: i128) -> u64 {
+        self.client.create_stream(
+            &self.sender,
+            &self.receiver,
+            &self.token_id,
+            &amount,
+            &0,
+            &1000,
+            &CurveType::Linear,
+            &false,
+        )
+    }
+}
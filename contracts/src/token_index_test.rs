@@ -0,0 +1,264 @@
+#![cfg(test)]
+
+use crate::{
+    test_helpers::{funded_token, setup, TestContext},
+    types::CurveType,
+    StellarStreamContractClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Vec,
+};
+
+fn create(
+    client: &StellarStreamContractClient,
+    sender: &Address,
+    receiver: &Address,
+    token: &Address,
+) -> u64 {
+    client.create_stream(
+        sender,
+        receiver,
+        token,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    )
+}
+
+#[test]
+fn test_index_separates_tokens() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        ..
+    } = setup();
+    let token_a = funded_token(&env, &sender, 10_000).address;
+    let token_b = funded_token(&env, &sender, 10_000).address;
+
+    let a0 = create(&client, &sender, &receiver, &token_a);
+    let b0 = create(&client, &sender, &receiver, &token_b);
+    let a1 = create(&client, &sender, &receiver, &token_a);
+
+    assert_eq!(
+        client.get_streams_by_token(&token_a, &0, &10),
+        vec![&env, a0, a1]
+    );
+    assert_eq!(
+        client.get_streams_by_token(&token_b, &0, &10),
+        vec![&env, b0]
+    );
+
+    let unused = Address::generate(&env);
+    assert_eq!(client.get_streams_by_token(&unused, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_index_pagination() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        ..
+    } = setup();
+    let token = funded_token(&env, &sender, 10_000).address;
+    let ids: Vec<u64> = vec![
+        &env,
+        create(&client, &sender, &receiver, &token),
+        create(&client, &sender, &receiver, &token),
+        create(&client, &sender, &receiver, &token),
+    ];
+
+    assert_eq!(
+        client.get_streams_by_token(&token, &0, &2),
+        vec![&env, ids.get(0).unwrap(), ids.get(1).unwrap()]
+    );
+    assert_eq!(
+        client.get_streams_by_token(&token, &2, &2),
+        vec![&env, ids.get(2).unwrap()]
+    );
+    assert_eq!(client.get_streams_by_token(&token, &5, &2).len(), 0);
+}
+
+#[test]
+fn test_cancel_removes_from_index() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        ..
+    } = setup();
+    let token = funded_token(&env, &sender, 10_000).address;
+
+    let first = create(&client, &sender, &receiver, &token);
+    let second = create(&client, &sender, &receiver, &token);
+
+    client.cancel(&first, &sender);
+
+    assert_eq!(
+        client.get_streams_by_token(&token, &0, &10),
+        vec![&env, second]
+    );
+}
+
+#[test]
+fn test_cancel_and_completion_purge_stream_storage() {
+    use crate::types::{DataKey, StreamIndex, StreamOptions};
+    use soroban_sdk::BytesN;
+
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        ..
+    } = setup();
+    let token = funded_token(&env, &sender, 10_000).address;
+    let keys = [1u8, 2].map(|byte| BytesN::from_array(&env, &[byte; 32]));
+    let [cancelled, completed] = keys.clone().map(|key| {
+        let options = StreamOptions {
+            is_soulbound: false,
+            idempotency_key: Some(key),
+            mutual_cancel: false,
+            revert_after: None,
+            on_cancel_hook: false,
+        };
+        client.create_stream_with_options(
+            &sender,
+            &receiver,
+            &token,
+            &1000,
+            &0,
+            &1000,
+            &CurveType::Linear,
+            &options,
+        )
+    });
+
+    let delegate = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        for id in [cancelled, completed] {
+            let storage = env.storage().instance();
+            storage.set(&DataKey::VotingDelegate(id), &delegate);
+        }
+    });
+
+    client.cancel(&cancelled, &sender);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.withdraw(&completed, &receiver);
+
+    // A late retry still resolves to the original stream
+    for (id, key) in [cancelled, completed].into_iter().zip(keys.clone()) {
+        let options = StreamOptions {
+            is_soulbound: false,
+            idempotency_key: Some(key),
+            mutual_cancel: false,
+            revert_after: None,
+            on_cancel_hook: false,
+        };
+        let retry = client.create_stream_with_options(
+            &sender,
+            &receiver,
+            &token,
+            &1000,
+            &1000,
+            &2000,
+            &CurveType::Linear,
+            &options,
+        );
+        assert_eq!(retry, id);
+    }
+
+    env.as_contract(&client.address, || {
+        let instance = env.storage().instance();
+        let persistent = env.storage().persistent();
+        for (id, key) in [cancelled, completed].into_iter().zip(keys.clone()) {
+            assert!(!instance.has(&DataKey::VotingDelegate(id)));
+            assert!(persistent.has(&DataKey::IdempotencyKey(sender.clone(), key)));
+        }
+        for index in [
+            StreamIndex::Token(token.clone()),
+            StreamIndex::Receiver(receiver.clone()),
+        ] {
+            assert!(!persistent.has(&DataKey::IndexBuckets(index.clone())));
+            assert!(!persistent.has(&DataKey::IndexBucket(index, 0)));
+        }
+    });
+    assert_eq!(client.get_streams_by_token(&token, &0, &10).len(), 0);
+
+    // The stream records themselves are kept
+    assert!(client.get_stream(&cancelled).cancelled);
+    assert_eq!(client.get_stream(&completed).withdrawn_amount, 1000);
+}
+
+#[test]
+fn test_index_spans_buckets() {
+    use crate::{
+        storage::STREAM_COUNT,
+        types::{DataKey, StreamIndex},
+        EXTEND_LEDGERS,
+    };
+    use soroban_sdk::testutils::storage::Persistent as _;
+
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        ..
+    } = setup();
+    let token = funded_token(&env, &sender, 10_000).address;
+
+    // Start just below a bucket boundary
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&STREAM_COUNT, &62u64);
+    });
+    for _ in 0..4 {
+        create(&client, &sender, &receiver, &token);
+    }
+
+    assert_eq!(
+        client.get_streams_by_token(&token, &0, &10),
+        vec![&env, 62, 63, 64, 65]
+    );
+    assert_eq!(
+        client.get_streams_by_token(&token, &0, &2),
+        vec![&env, 62, 63]
+    );
+    // Continue from the last id returned plus one
+    assert_eq!(
+        client.get_streams_by_token(&token, &64, &2),
+        vec![&env, 64, 65]
+    );
+    assert_eq!(client.get_streams_by_token(&token, &66, &2).len(), 0);
+
+    let index = StreamIndex::Token(token.clone());
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        let buckets: Vec<u32> = storage.get(&DataKey::IndexBuckets(index.clone())).unwrap();
+        assert_eq!(buckets, vec![&env, 0, 1]);
+        // Written entries live as long as the instance
+        let key = DataKey::IndexBucket(index.clone(), 1);
+        assert_eq!(storage.get_ttl(&key), EXTEND_LEDGERS);
+    });
+
+    // Emptying a bucket drops it from the index
+    client.cancel(&62, &sender);
+    client.cancel(&63, &sender);
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        assert!(!storage.has(&DataKey::IndexBucket(index.clone(), 0)));
+        let buckets: Vec<u32> = storage.get(&DataKey::IndexBuckets(index.clone())).unwrap();
+        assert_eq!(buckets, vec![&env, 1]);
+    });
+    assert_eq!(
+        client.get_streams_by_token(&token, &0, &10),
+        vec![&env, 64, 65]
+    );
+}
@@ -472,3 +472,44 @@ fn test_cancelled_stream_ttl_and_recreation() {
         "New stream should be completed, not cancelled"
     );
 }
+
+/// Test that transferring the receiver refreshes the lifetime of stream storage
+#[test]
+fn test_transfer_receiver_extends_ttl() {
+    use soroban_sdk::testutils::storage::Instance as _;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 100_000;
+        li.min_persistent_entry_ttl = 4_096;
+        li.max_entry_ttl = 10_000_000;
+    });
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sender = Address::generate(&env);
+    let receiver = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_id).mint(&sender, &1000);
+
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+    let ttl_before = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
+    assert!(ttl_before < 6_000_000);
+
+    client.transfer_receiver(&stream_id, &sender, &Address::generate(&env));
+
+    let ttl_after = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
+    assert_eq!(ttl_after, 6_000_000);
+}
@@ -21,6 +21,32 @@ pub const INTEREST_SPLIT_ALL: u32 = 0b111; // 7: 33/33/33 split
 pub enum CurveType {
     Linear = 0,
     Exponential = 1,
+    /// Interpolated between `CurveParams::checkpoints`
+    Custom = 2,
+    /// Equal tranches every `CurveParams::step_interval` seconds
+    Step = 3,
+}
+
+/// Shape of the curves that need more than a `CurveType`; Linear and Exponential
+/// streams leave it empty. Kept out of `CurveType` so the enum stays a plain u32.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CurveParams {
+    /// `(elapsed_bps, unlocked_bps)` checkpoints, interpolated linearly between points.
+    /// Must run from (0, 0) to (10000, 10000) with `elapsed_bps` strictly increasing
+    /// and `unlocked_bps` non-decreasing.
+    pub checkpoints: Vec<(u32, u32)>,
+    /// Unlocks in equal tranches at the end of every interval of this many seconds
+    pub step_interval: u64,
+}
+
+/// An id index kept by the contract. Each is stored as sorted buckets of stream ids,
+/// so adding or removing a stream rewrites one bucket instead of the whole index.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StreamIndex {
+    Token(Address),    // Open streams of a token
+    Receiver(Address), // Open streams paying a receiver
 }
 
 // Role definitions for RBAC
@@ -49,6 +75,15 @@ pub struct UsdPegConfig {
     pub oracle: PriceOracle,
 }
 
+/// Current holders of each governance role, as returned by `get_governance`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Governance {
+    pub admins: Vec<Address>,
+    pub pausers: Vec<Address>,
+    pub treasury_managers: Vec<Address>,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct Milestone {
@@ -97,10 +132,56 @@ pub struct Stream {
     pub arbiter: Option<Address>,
     /// If true, stream is frozen pending dispute resolution
     pub is_frozen: bool,
+    /// If true, cancellation needs authorization from both sender and receiver
+    pub mutual_cancel: bool,
+    /// If true, nothing was deposited: withdrawals pull from the sender's allowance
+    pub is_allowance: bool,
+    /// After this time the sender may reclaim whatever the receiver never withdrew
+    pub revert_after: Option<u64>,
+    /// If true, the receiver contract is notified through `on_stream_cancelled`
+    pub on_cancel_hook: bool,
+    /// Party (sender or receiver) that paused the stream, while it is paused
+    pub paused_by: Option<Address>,
+    /// Checkpoints or step interval for `CurveType::Custom` and `CurveType::Step`
+    pub curve_params: CurveParams,
+}
+
+/// `Stream` as stored by v1 deployments, before mutual_cancel, is_allowance,
+/// revert_after, on_cancel_hook, paused_by and curve_params. Only read by `migrate`.
+#[contracttype]
+#[derive(Clone)]
+pub struct StreamV1 {
+    pub sender: Address,
+    pub receiver: Address,
+    pub token: Address,
+    pub total_amount: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub withdrawn: i128,
+    pub withdrawn_amount: i128,
+    pub cancelled: bool,
+    pub receipt_owner: Address,
+    pub is_paused: bool,
+    pub paused_time: u64,
+    pub total_paused_duration: u64,
+    pub milestones: Vec<Milestone>,
+    pub curve_type: CurveType,
+    pub interest_strategy: u32,
+    pub vault_address: Option<Address>,
+    pub deposited_principal: i128,
+    pub metadata: Option<BytesN<32>>,
+    pub is_usd_pegged: bool,
+    pub usd_amount: i128,
+    pub oracle_address: Address,
+    pub oracle_max_staleness: u64,
+    pub price_min: i128,
+    pub price_max: i128,
+    pub is_soulbound: bool,
+    pub clawback_enabled: bool,
+    pub arbiter: Option<Address>,
+    pub is_frozen: bool,
 }
 
-// Legacy Stream struct (v1) - for migration example
-// This represents an older version without cliff_time
 #[contracttype]
 #[derive(Clone)]
 pub struct StreamProposal {
@@ -129,6 +210,30 @@ pub struct StreamRequest {
     pub metadata: Option<BytesN<32>>,
 }
 
+/// A `StreamRequest` funded in its own `token`, for `create_multi_token_batch`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultiTokenStreamRequest {
+    pub token: Address,
+    pub request: StreamRequest,
+}
+
+/// Optional settings for `create_stream_with_options`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamOptions {
+    /// Permanently bind the stream to its receiver
+    pub is_soulbound: bool,
+    /// Relayer retry guard: the same sender repeating a key gets the original stream id
+    pub idempotency_key: Option<BytesN<32>>,
+    /// Only allow cancellation with both sender and receiver signing
+    pub mutual_cancel: bool,
+    /// Let the sender reclaim unwithdrawn funds from this time on (at or after `end_time`)
+    pub revert_after: Option<u64>,
+    /// Call `on_stream_cancelled` on the receiver contract when the stream is cancelled
+    pub on_cancel_hook: bool,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InterestDistribution {
@@ -148,13 +253,37 @@ pub enum DataKey {
     Treasury,
     IsPaused,
     ReentrancyLock,
-    ContractVersion,        // Tracks current contract version
-    MigrationExecuted(u32), // Tracks which migrations have been executed
-    Role(Address, Role),    // RBAC: stores role assignments
-    SoulboundStreams,       // Vec<u64> of all soulbound stream IDs
-    ApprovedVaults,         // Vec<Address> of approved lending vaults
-    VaultShares(u64),       // Vault shares for stream_id
-    VotingDelegate(u64),    // Voting delegate for stream_id
+    ContractVersion,                     // Tracks current contract version
+    MigrationExecuted(u32),              // Tracks which migrations have been executed
+    Role(Address, Role),                 // RBAC: stores role assignments
+    SoulboundStreams,                    // Vec<u64> of all soulbound stream IDs
+    ApprovedVaults,                      // Vec<Address> of approved lending vaults
+    VaultShares(u64),                    // Vault shares for stream_id
+    VotingDelegate(u64),                 // Voting delegate for stream_id
+    CreationCount(Address),              // (window bucket, streams created) per sender
+    MaxStreamsPerWindow,                 // Admin-set cap on creations per sender per window
+    TreasurySplits,                      // Vec<(Address, u32)> of fee recipients and their bps
+    AllowCancelWhilePaused,              // If true, cancel is allowed while the contract is paused
+    PausedAt,                            // Timestamp of the current contract pause
+    MaxPauseDuration,                    // Seconds after which a pause lapses automatically
+    IdempotencyKey(Address, BytesN<32>), // Stream id a sender created with an idempotency key
+    FeeToken,                            // If set, creation fees are charged in this token
+    IndexBuckets(StreamIndex),           // Sorted Vec<u32> of an index's non-empty buckets
+    IndexBucket(StreamIndex, u32),       // Sorted Vec<u64> of an index's ids in one bucket
+    RoleMembers(Role),                   // Vec<Address> holding a role
+    RoleAdmin(Role),                     // Role allowed to grant/revoke this role (default Admin)
+    TokenFeeBps(Address),                // Per-token fee override in bps
+    PendingFee,                          // (fee_bps, effective_at) scheduled global fee change
+    CancelAfterEndGrace,                 // Seconds after end_time during which cancel is allowed
+    UnpauseApprovals,                    // Vec<Address> of pausers who approved lifting the pause
+    UnpauseQuorum,                       // Pauser approvals needed to unpause (default 1)
+    MinDuration,                         // Minimum stream duration in seconds for new streams
+    DefaultCurve,                        // CurveType for creation paths without a curve argument
+    MaxBatchSize,                        // Cap on requests per batch creation call
+    UpgradeTimelock,                     // PendingUpgrade awaiting its delay
+    BatchSummaryOnly,                    // bool: batches emit only the summary event
+    TokenDecimals(Address),              // Cached `decimals()` of a token
+    TokenLiability(Address), // What open streams owe out of the contract's balance of a token
 }
 
 #[contracttype]
@@ -165,19 +294,6 @@ pub struct StreamReceipt {
     pub minted_at: u64,
 }
 
-#[contracttype]
-#[derive(Clone, Debug)]
-pub struct StreamCreatedEvent {
-    pub stream_id: u64,
-    pub sender: Address,
-    pub receiver: Address,
-    pub token: Address,
-    pub total_amount: i128,
-    pub start_time: u64,
-    pub end_time: u64,
-    pub timestamp: u64,
-}
-
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct StreamClaimEvent {
@@ -346,3 +462,11 @@ pub struct RequestExecutedEvent {
     pub executor: Address,
     pub timestamp: u64,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingUpgrade {
+    pub wasm_hash: BytesN<32>,
+    pub executable_at: u64,
+    pub stream_count: u64, // Stream id counter when proposed
+}
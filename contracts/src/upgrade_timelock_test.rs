@@ -0,0 +1,192 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    storage::STREAM_COUNT,
+    test_helpers::{setup, TestContext},
+    types::CurveType,
+    UPGRADE_DELAY_SECS,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN,
+};
+
+// The WASM swap itself needs the code uploaded to the network first, which unit
+// tests cannot do. These tests cover everything `upgrade` checks before the swap.
+
+#[test]
+fn test_upgrade_requires_proposal() {
+    let TestContext {
+        env, client, admin, ..
+    } = setup();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    assert_eq!(
+        client.try_upgrade(&admin, &hash),
+        Err(Ok(Error::UpgradeNotProposed))
+    );
+    assert_eq!(
+        client.try_execute_upgrade(&admin),
+        Err(Ok(Error::UpgradeNotProposed))
+    );
+
+    // Only the proposed hash can be installed
+    client.propose_upgrade(&admin, &hash);
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1_000 + UPGRADE_DELAY_SECS);
+    let other = BytesN::from_array(&env, &[2u8; 32]);
+    assert_eq!(
+        client.try_upgrade(&admin, &other),
+        Err(Ok(Error::UpgradeNotProposed))
+    );
+}
+
+#[test]
+fn test_two_step_upgrade_waits_for_delay() {
+    let TestContext {
+        env, client, admin, ..
+    } = setup();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    let executable_at = client.propose_upgrade(&admin, &hash);
+    assert_eq!(executable_at, 1_000 + UPGRADE_DELAY_SECS);
+    let pending = client.get_pending_upgrade().unwrap();
+    assert_eq!(pending.wasm_hash, hash);
+    assert_eq!(pending.executable_at, executable_at);
+
+    env.ledger().with_mut(|li| li.timestamp = executable_at - 1);
+    assert_eq!(
+        client.try_execute_upgrade(&admin),
+        Err(Ok(Error::UpgradeTimelocked))
+    );
+    assert_eq!(
+        client.try_upgrade(&admin, &hash),
+        Err(Ok(Error::UpgradeTimelocked))
+    );
+
+    // Once the delay is over every contract check passes and the host attempts
+    // the swap, which fails here only because the hash was never uploaded
+    env.ledger().with_mut(|li| li.timestamp = executable_at);
+    assert!(matches!(client.try_execute_upgrade(&admin), Err(Err(_))));
+    assert!(matches!(client.try_upgrade(&admin, &hash), Err(Err(_))));
+    assert_eq!(client.get_pending_upgrade(), Some(pending));
+}
+
+#[test]
+fn test_reproposing_restarts_delay() {
+    let TestContext {
+        env, client, admin, ..
+    } = setup();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.propose_upgrade(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1_000 + UPGRADE_DELAY_SECS);
+
+    let hash = BytesN::from_array(&env, &[2u8; 32]);
+    client.propose_upgrade(&admin, &hash);
+    assert_eq!(
+        client.try_upgrade(&admin, &hash),
+        Err(Ok(Error::UpgradeTimelocked))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Upgrade invariant violated")]
+fn test_upgrade_rejects_inconsistent_stream_counter() {
+    let TestContext {
+        env,
+        client,
+        admin,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &1_000,
+        &2_000,
+        &CurveType::Linear,
+        &false,
+    );
+
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    client.propose_upgrade(&admin, &hash);
+
+    // Simulate a counter reset: the next create would overwrite stream 0
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&STREAM_COUNT, &0u64);
+    });
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1_000 + UPGRADE_DELAY_SECS);
+    client.upgrade(&admin, &hash);
+}
+
+#[test]
+#[should_panic(expected = "Upgrade invariant violated: liability exceeds balance")]
+fn test_upgrade_rejects_insolvent_contract() {
+    let TestContext {
+        env,
+        client,
+        admin,
+        sender,
+        receiver,
+        token_id,
+        token,
+        ..
+    } = setup();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &1_000,
+        &2_000,
+        &CurveType::Linear,
+        &false,
+    );
+
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    client.propose_upgrade(&admin, &hash);
+
+    // Simulate funds leaking out while the stream still owes them
+    env.as_contract(&client.address, || {
+        token.transfer(&client.address, &Address::generate(&env), &1);
+    });
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1_000 + UPGRADE_DELAY_SECS);
+    client.upgrade(&admin, &hash);
+}
+
+#[test]
+fn test_non_admin_cannot_propose_or_execute() {
+    let TestContext { env, client, .. } = setup();
+    let intruder = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    assert_eq!(
+        client.try_propose_upgrade(&intruder, &hash),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_upgrade(&intruder, &hash),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_execute_upgrade(&intruder),
+        Err(Ok(Error::Unauthorized))
+    );
+}
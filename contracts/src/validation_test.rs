@@ -0,0 +1,195 @@
+#![cfg(test)]
+
+use crate::{
+    errors::Error,
+    test_helpers::{setup, TestContext},
+    types::{CurveType, Milestone},
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Vec,
+};
+
+/// Runs the same parameters through `create_stream` and `create_stream_with_milestones`
+/// and checks both paths return the same error.
+fn assert_both_paths_fail(ctx: &TestContext, amount: i128, start: u64, end: u64, expected: Error) {
+    let result = ctx.client.try_create_stream(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token_id,
+        &amount,
+        &start,
+        &end,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(result, Err(Ok(expected)));
+
+    let result = ctx.client.try_create_stream_with_milestones(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token_id,
+        &amount,
+        &start,
+        &end,
+        &Vec::new(&ctx.env),
+        &CurveType::Exponential,
+        &false,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(expected)));
+
+    // Nothing moved on either path
+    assert_eq!(ctx.token.balance(&ctx.sender), 10000);
+}
+
+fn try_with_milestones(ctx: &TestContext, milestones: Vec<Milestone>) -> Option<Error> {
+    match ctx.client.try_create_stream_with_milestones(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token_id,
+        &1000,
+        &100,
+        &500,
+        &milestones,
+        &CurveType::Linear,
+        &false,
+        &None,
+    ) {
+        Ok(_) => None,
+        Err(Ok(err)) => Some(err),
+        Err(Err(_)) => panic!("unexpected host error"),
+    }
+}
+
+#[test]
+fn test_end_before_start_rejected() {
+    let ctx = setup();
+    assert_both_paths_fail(&ctx, 1000, 500, 100, Error::InvalidTimeRange);
+}
+
+#[test]
+fn test_zero_duration_rejected() {
+    let ctx = setup();
+    assert_both_paths_fail(&ctx, 1000, 100, 100, Error::InvalidTimeRange);
+}
+
+#[test]
+fn test_non_positive_amount_rejected() {
+    let ctx = setup();
+    assert_both_paths_fail(&ctx, 0, 100, 500, Error::InvalidAmount);
+    assert_both_paths_fail(&ctx, -5, 100, 500, Error::InvalidAmount);
+}
+
+#[test]
+fn test_milestone_validation() {
+    let ctx = setup();
+
+    // Valid schedule
+    let ok = vec![
+        &ctx.env,
+        Milestone {
+            timestamp: 200,
+            percentage: 50,
+        },
+        Milestone {
+            timestamp: 500,
+            percentage: 100,
+        },
+    ];
+    assert_eq!(try_with_milestones(&ctx, ok), None);
+
+    // Percentage above 100
+    let too_high = vec![
+        &ctx.env,
+        Milestone {
+            timestamp: 200,
+            percentage: 101,
+        },
+    ];
+    assert_eq!(
+        try_with_milestones(&ctx, too_high),
+        Some(Error::InvalidMilestone)
+    );
+
+    // Before the stream starts
+    let too_early = vec![
+        &ctx.env,
+        Milestone {
+            timestamp: 50,
+            percentage: 10,
+        },
+    ];
+    assert_eq!(
+        try_with_milestones(&ctx, too_early),
+        Some(Error::InvalidMilestone)
+    );
+
+    // After the stream ends
+    let too_late = vec![
+        &ctx.env,
+        Milestone {
+            timestamp: 600,
+            percentage: 100,
+        },
+    ];
+    assert_eq!(
+        try_with_milestones(&ctx, too_late),
+        Some(Error::InvalidMilestone)
+    );
+
+    // Out of order
+    let unordered = vec![
+        &ctx.env,
+        Milestone {
+            timestamp: 300,
+            percentage: 50,
+        },
+        Milestone {
+            timestamp: 200,
+            percentage: 25,
+        },
+    ];
+    assert_eq!(
+        try_with_milestones(&ctx, unordered),
+        Some(Error::InvalidMilestone)
+    );
+}
+
+#[test]
+fn test_executed_proposal_builds_same_stream() {
+    let TestContext {
+        env,
+        client,
+        sender,
+        receiver,
+        token_id,
+        ..
+    } = setup();
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    let approver = Address::generate(&env);
+
+    let proposal_id =
+        client.create_proposal(&sender, &receiver, &token_id, &1000, &100, &200, &1, &1000);
+    client.approve_proposal(&proposal_id, &approver);
+
+    let direct_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_id,
+        &1000,
+        &100,
+        &200,
+        &CurveType::Linear,
+        &false,
+    );
+
+    let from_proposal = client.get_stream(&(direct_id - 1));
+    let direct = client.get_stream(&direct_id);
+    assert_eq!(from_proposal.total_amount, direct.total_amount);
+    assert_eq!(from_proposal.start_time, direct.start_time);
+    assert_eq!(from_proposal.end_time, direct.end_time);
+    assert_eq!(from_proposal.curve_type, direct.curve_type);
+    assert_eq!(from_proposal.receipt_owner, direct.receipt_owner);
+}
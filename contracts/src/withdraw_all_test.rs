@@ -0,0 +1,146 @@
+#![cfg(test)]
+
+use crate::{
+    test_helpers::{funded_token, setup_with_balance, TestContext},
+    types::CurveType,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::TokenClient,
+    vec, Address,
+};
+
+fn create(
+    ctx: &TestContext,
+    receiver: &Address,
+    token: &TokenClient,
+    amount: i128,
+    start: u64,
+    end: u64,
+) -> u64 {
+    ctx.client.create_stream(
+        &ctx.sender,
+        receiver,
+        &token.address,
+        &amount,
+        &start,
+        &end,
+        &CurveType::Linear,
+        &false,
+    )
+}
+
+#[test]
+fn test_withdraw_all_claims_across_tokens() {
+    let ctx = setup_with_balance(100_000);
+    let token_b = funded_token(&ctx.env, &ctx.sender, 100_000);
+    let other = Address::generate(&ctx.env);
+
+    let a1 = create(&ctx, &ctx.receiver, &ctx.token, 1000, 0, 1000);
+    let b1 = create(&ctx, &ctx.receiver, &token_b, 4000, 0, 1000);
+    let a2 = create(&ctx, &ctx.receiver, &ctx.token, 2000, 0, 500);
+    let future = create(&ctx, &ctx.receiver, &token_b, 1000, 800, 1800);
+    create(&ctx, &other, &ctx.token, 1000, 0, 1000);
+
+    assert_eq!(
+        ctx.client.get_streams_by_receiver(&ctx.receiver, &0, &10),
+        vec![&ctx.env, a1, b1, a2, future]
+    );
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(
+        ctx.client.withdraw_all(&ctx.receiver, &0, &10),
+        500 + 2000 + 2000
+    );
+
+    assert_eq!(ctx.token.balance(&ctx.receiver), 2500);
+    assert_eq!(token_b.balance(&ctx.receiver), 2000);
+    assert_eq!(token_b.balance(&other), 0);
+    assert_eq!(ctx.client.get_stream(&future).withdrawn_amount, 0);
+
+    // The finished stream drops out of the index
+    assert_eq!(
+        ctx.client.get_streams_by_receiver(&ctx.receiver, &0, &10),
+        vec![&ctx.env, a1, b1, future]
+    );
+
+    // Nothing new has unlocked yet
+    assert_eq!(ctx.client.withdraw_all(&ctx.receiver, &0, &10), 0);
+}
+
+#[test]
+fn test_withdraw_all_caps_the_page() {
+    let ctx = setup_with_balance(100_000);
+    let mut ids = vec![&ctx.env];
+    for _ in 0..22 {
+        ids.push_back(create(&ctx, &ctx.receiver, &ctx.token, 100, 0, 100));
+    }
+
+    // A larger limit is clamped to MAX_WITHDRAW_ALL
+    ctx.env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(ctx.client.withdraw_all(&ctx.receiver, &0, &50), 2000);
+    assert_eq!(
+        ctx.client
+            .get_stream(&ids.get(19).unwrap())
+            .withdrawn_amount,
+        100
+    );
+    assert_eq!(
+        ctx.client
+            .get_stream(&ids.get(20).unwrap())
+            .withdrawn_amount,
+        0
+    );
+
+    // The next page picks up where the first stopped
+    let next = ids.get(19).unwrap() + 1;
+    assert_eq!(ctx.client.withdraw_all(&ctx.receiver, &next, &50), 200);
+    assert_eq!(ctx.token.balance(&ctx.receiver), 2200);
+}
+
+#[test]
+fn test_idle_streams_count_toward_the_page() {
+    let ctx = setup_with_balance(100_000);
+    let token_b = funded_token(&ctx.env, &ctx.sender, 100_000);
+    // Older streams that pay nothing yet: not started, or paused
+    for _ in 0..19 {
+        create(&ctx, &ctx.receiver, &ctx.token, 100, 5_000, 6_000);
+    }
+    let paused = create(&ctx, &ctx.receiver, &ctx.token, 100, 0, 100);
+    ctx.client.pause_stream(&paused, &ctx.sender);
+    let live = create(&ctx, &ctx.receiver, &token_b, 1000, 0, 1000);
+
+    // The first page is all idle streams, so nothing is paid
+    ctx.env.ledger().with_mut(|li| li.timestamp = 400);
+    assert_eq!(ctx.client.withdraw_all(&ctx.receiver, &0, &20), 0);
+    assert_eq!(
+        ctx.client.withdraw_all(&ctx.receiver, &(paused + 1), &20),
+        400
+    );
+    assert_eq!(ctx.client.get_stream(&live).withdrawn_amount, 400);
+    assert_eq!(token_b.balance(&ctx.receiver), 400);
+}
+
+#[test]
+fn test_transfer_receiver_moves_index_entry() {
+    let ctx = setup_with_balance(100_000);
+    let new_receiver = Address::generate(&ctx.env);
+    let first = create(&ctx, &ctx.receiver, &ctx.token, 1000, 0, 1000);
+    let second = create(&ctx, &new_receiver, &ctx.token, 1000, 0, 1000);
+
+    ctx.client
+        .transfer_receiver(&first, &ctx.sender, &new_receiver);
+    assert_eq!(
+        ctx.client
+            .get_streams_by_receiver(&ctx.receiver, &0, &10)
+            .len(),
+        0
+    );
+    assert_eq!(
+        ctx.client.get_streams_by_receiver(&new_receiver, &0, &10),
+        vec![&ctx.env, first, second]
+    );
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = 250);
+    assert_eq!(ctx.client.withdraw_all(&new_receiver, &0, &10), 500);
+}